@@ -1,13 +1,30 @@
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    pin::pin,
+    sync::{Arc, RwLock},
+};
 
+use futures::{FutureExt, Stream, TryStreamExt};
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
 
-use crate::client::{error::HubspotResult, HubspotClient};
+use crate::{
+    client::{
+        error::{BatchError, HubspotError, HubspotResult},
+        HubspotClient,
+    },
+    objects::ObjectType,
+};
 
 use super::{
-    query::build_paging_query,
-    types::{ListResult, ObjectApi, ToPath},
+    batch::{send_batch, BatchErrorPolicy, BatchOptions, BatchResponse, BatchStatus},
+    paging::{paginate, PagingOptions},
+    query::Query,
+    types::{
+        AssociationLinks, AssociationResults, ListResult, ObjectApi, OptionNotDesired, Paging,
+        ToPath,
+    },
 };
 
 /// An association linking an object `to_object_id` to the parent object.
@@ -22,7 +39,7 @@ pub struct Association {
 }
 
 /// An association type that represents the relationship between two records.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AssociationTypes {
     // Whether the association type was created by HubSpot or a user (HUBSPOT_DEFINED and USER_DEFINED)
     pub category: String,
@@ -34,7 +51,7 @@ pub struct AssociationTypes {
 }
 
 /// A struct for creating new associations.
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AssociationCreationDetails {
     /// Whether the association type was created by HubSpot or a user (HUBSPOT_DEFINED and USER_DEFINED)
     #[serde(rename = "associationCategory")]
@@ -44,6 +61,50 @@ pub struct AssociationCreationDetails {
     pub type_id: i64,
 }
 
+impl From<&AssociationTypes> for AssociationCreationDetails {
+    fn from(association_type: &AssociationTypes) -> Self {
+        Self {
+            category: association_type.category.clone(),
+            type_id: association_type.type_id,
+        }
+    }
+}
+
+impl From<&AssociationLinks> for AssociationCreationDetails {
+    fn from(link: &AssociationLinks) -> Self {
+        Self {
+            category: "HUBSPOT_DEFINED".to_owned(),
+            type_id: link.type_id(),
+        }
+    }
+}
+
+/// The input to define a new association label.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct AssociationLabelInput<'a> {
+    /// The label shown in Hubspot, eg. "Decision maker".
+    label: &'a str,
+    /// The internal name of the label.
+    name: &'a str,
+    /// The label of the reverse direction, for labels that differ by direction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    inverse_label: Option<&'a str>,
+}
+
+/// The changes to make to an association label.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct AssociationLabelUpdate<'a> {
+    /// The numeric ID of the association type to update.
+    association_type_id: i64,
+    /// The label shown in Hubspot.
+    label: &'a str,
+    /// The label of the reverse direction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    inverse_label: Option<&'a str>,
+}
+
 /// A  Hubspot result type for a created association.
 #[derive(Deserialize, Debug)]
 pub struct CreatedAssociationResult {
@@ -57,12 +118,232 @@ pub struct CreatedAssociationResult {
     #[serde(alias = "toObjectId")]
     pub to_object_id: i64,
     /// Association labels describe relationships between all standard CRM objects
+    #[serde(default)]
     pub labels: Vec<String>,
 }
 
+/// A default, unlabeled association created between two records.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DefaultAssociation {
+    /// The record the association is from.
+    pub from: AssociationObjectId,
+    /// The record the association is to.
+    pub to: AssociationObjectId,
+    /// The association type of the default association.
+    pub association_spec: AssociationCreationDetails,
+}
+
+/// The ID of a record in a batch association request.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct AssociationObjectId {
+    /// The ID of the record.
+    #[serde(deserialize_with = "serde_aux::field_attributes::deserialize_string_from_number")]
+    pub id: String,
+}
+
+/// A pair of records to associate, or whose association labels to remove, in a batch request.
+#[derive(Serialize, Debug, Clone)]
+pub struct AssociationPair {
+    /// The record of the collection's object type.
+    pub from: AssociationObjectId,
+    /// The record of the associated object type.
+    pub to: AssociationObjectId,
+    /// The association types of the pair.
+    pub types: Vec<AssociationCreationDetails>,
+}
+
+impl AssociationPair {
+    /// Create a pair of records with the given association types.
+    pub fn new(from_id: &str, to_id: &str, types: Vec<AssociationCreationDetails>) -> Self {
+        Self {
+            from: AssociationObjectId {
+                id: from_id.to_owned(),
+            },
+            to: AssociationObjectId {
+                id: to_id.to_owned(),
+            },
+            types,
+        }
+    }
+}
+
+/// The associations of one record, as returned by a batch read.
+#[derive(Deserialize, Debug)]
+pub struct RecordAssociations {
+    /// The record the associations were read for.
+    pub from: AssociationObjectId,
+    /// The associated records.
+    pub to: Vec<Association>,
+    /// Paging information, when the record has more associations.
+    pub paging: Option<Paging>,
+}
+
+/// A record to read the associations of in a batch request.
+#[derive(Serialize, Debug)]
+struct AssociationReadInput {
+    /// The ID of the record.
+    id: String,
+}
+
+/// A pair of records to give the default association in a batch request.
+#[derive(Serialize, Debug)]
+struct AssociationDefaultInput {
+    /// The record of the collection's object type.
+    from: AssociationObjectId,
+    /// The record of the associated object type.
+    to: AssociationObjectId,
+}
+
+/// The records to remove every association between in a batch request.
+#[derive(Serialize, Debug)]
+struct AssociationArchiveInput {
+    /// The record of the collection's object type.
+    from: AssociationObjectId,
+    /// The records of the associated object type.
+    to: Vec<AssociationObjectId>,
+}
+
+/// A wrapper type for batch association inputs.
+#[derive(Serialize, Debug)]
+struct AssociationBatchInputs<'a, I> {
+    /// The inputs for a Batch request.
+    inputs: &'a [I],
+}
+
+/// A Hubspot result type for a batch association request.
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AssociationBatchResult<R> {
+    /// The status result of the batch request.
+    #[serde(default)]
+    pub status: String,
+    /// The result objects of the batch request.
+    pub results: Vec<R>,
+    /// The number of errors, when some pairs failed.
+    #[serde(default)]
+    pub num_errors: usize,
+    /// The errors of the pairs that failed, returned with a 207 Multi-Status response.
+    #[serde(default)]
+    pub errors: Vec<BatchError>,
+    /// The time the batch request was requested.
+    #[serde(default)]
+    pub requested_at: String,
+    /// The time the batch request started.
+    #[serde(default)]
+    pub started_at: String,
+    /// The time the batch request was completed at.
+    #[serde(default)]
+    pub completed_at: String,
+    /// Links for the batch request.
+    #[serde(default)]
+    pub links: HashMap<String, String>,
+}
+
+impl<R> BatchResponse for AssociationBatchResult<R> {
+    fn merge(chunks: Vec<Self>) -> Self {
+        let mut status = BatchStatus::default();
+        let mut results = Vec::new();
+        let mut errors = Vec::new();
+        let mut links = HashMap::new();
+
+        for chunk in chunks {
+            status.merge(
+                chunk.status,
+                chunk.requested_at,
+                chunk.started_at,
+                chunk.completed_at,
+            );
+            results.extend(chunk.results);
+            errors.extend(chunk.errors);
+            links.extend(chunk.links);
+        }
+
+        Self {
+            status: status.status,
+            results,
+            num_errors: errors.len(),
+            errors,
+            requested_at: status.requested_at,
+            started_at: status.started_at,
+            completed_at: status.completed_at,
+            links,
+        }
+    }
+
+    fn errors_mut(&mut self) -> &mut Vec<BatchError> {
+        &mut self.errors
+    }
+}
+
+/// The outcome of setting the primary company of a record.
+#[derive(Debug)]
+pub struct PrimaryCompanyChange {
+    /// The ID of the previous primary company, if the record had one.
+    pub previous: Option<String>,
+    /// The error of cleaning up the previous primary company, after the new one was set.
+    /// Until the cleanup is done again, the record has two companies labeled primary.
+    pub cleanup_error: Option<HubspotError>,
+}
+
+impl PrimaryCompanyChange {
+    /// The previous primary company, when it differs from the new one.
+    fn replaced(&self, company_id: &str) -> Option<&str> {
+        self.previous
+            .as_deref()
+            .filter(|previous| *previous != company_id)
+    }
+}
+
+/// Find an association label by name, ignoring ASCII case.
+fn find_label(labels: &[AssociationTypes], label: &str) -> Option<AssociationCreationDetails> {
+    labels
+        .iter()
+        .find(|association_type| {
+            association_type
+                .label
+                .as_deref()
+                .is_some_and(|name| name.eq_ignore_ascii_case(label))
+        })
+        .map(AssociationCreationDetails::from)
+}
+
+/// Group the associations read with a record by associated record,
+/// or `None` if any is not of a Hubspot defined type.
+fn group_associations(associations: &AssociationResults) -> Option<Vec<Association>> {
+    let mut grouped: Vec<Association> = Vec::new();
+    for association in &associations.results {
+        let link = association.link()?;
+        let to_object_id = association.id.parse().ok()?;
+        let association_type = AssociationTypes {
+            category: "HUBSPOT_DEFINED".to_owned(),
+            type_id: link.type_id(),
+            label: link.is_primary().then(|| "Primary".to_owned()),
+        };
+
+        match grouped
+            .iter_mut()
+            .find(|associated| associated.to_object_id == to_object_id)
+        {
+            Some(associated) => associated.association_types.push(association_type),
+            None => grouped.push(Association {
+                to_object_id,
+                association_types: vec![association_type],
+            }),
+        }
+    }
+
+    Some(grouped)
+}
+
 // Association Api Collection
 #[derive(Clone, Debug)]
-pub struct AssociationsApiCollection<T>(T, Arc<HubspotClient>);
+pub struct AssociationsApiCollection<T>(
+    T,
+    Arc<HubspotClient>,
+    /// Association labels cached by the path of the associated object type.
+    Arc<RwLock<HashMap<String, Vec<AssociationTypes>>>>,
+);
 
 impl<T> ObjectApi<T> for AssociationsApiCollection<T>
 where
@@ -79,11 +360,11 @@ where
 
 impl<T> AssociationsApiCollection<T>
 where
-    T: ToPath,
+    T: Clone + ToPath,
 {
     /// Constructs a new AssociationsApiCollection for an object type.
     pub fn new(name: T, client: Arc<HubspotClient>) -> Self {
-        Self(name, client)
+        Self(name, client, Arc::new(RwLock::new(HashMap::new())))
     }
 
     /// List all associations of a record by object type. Limit 1000 per call.
@@ -98,7 +379,9 @@ where
         // The paging cursor token of the last successfully read resource will be returned as the paging.next.after JSON property of a paged response containing more results.
         after: Option<&str>,
     ) -> HubspotResult<ListResult<Association>> {
-        let paging_query = build_paging_query(limit, after);
+        let query = Query::new()
+            .push_opt("limit", limit)
+            .push_opt("after", after);
 
         self.client()
             .send::<ListResult<Association>>(self.client().begin(
@@ -108,12 +391,66 @@ where
                     self.path(),
                     id,
                     to_object_type,
-                    paging_query.0
+                    query
                 ),
             ))
             .await
     }
 
+    /// Stream every association of a record to an object type, fetching pages lazily as the stream is consumed.
+    pub fn list_all(
+        &self,
+        // The ID of the record retrieve the associations for.
+        id: &str,
+        // The type of object to retrieve associations for.
+        to_object_type: &str,
+        options: PagingOptions,
+    ) -> impl Stream<Item = HubspotResult<Association>> {
+        let collection = self.clone();
+        let id = id.to_owned();
+        let to_object_type = to_object_type.to_owned();
+
+        paginate(options, move |limit, after| {
+            let collection = collection.clone();
+            let id = id.clone();
+            let to_object_type = to_object_type.clone();
+            async move {
+                collection
+                    .list(&id, &to_object_type, limit, after.as_deref())
+                    .await
+            }
+        })
+    }
+
+    /// Returns every association of a record to an object type, given the associations read with the record,
+    /// eg. the `companies` of a contact read with `associations` requested.
+    ///
+    /// Complete associations of Hubspot defined types are returned as they are. Otherwise every association
+    /// is read with [AssociationsApiCollection::list_all], which fetches the remaining pages and the labels.
+    pub async fn complete<O>(
+        &self,
+        id: &str,
+        to_object_type: O,
+        associations: &AssociationResults,
+    ) -> HubspotResult<Vec<Association>>
+    where
+        O: ToPath,
+    {
+        if associations.is_complete() {
+            if let Some(associations) = group_associations(associations) {
+                return Ok(associations);
+            }
+        }
+
+        self.list_all(
+            id,
+            &to_object_type.to_path(),
+            PagingOptions::new().page_size(500),
+        )
+        .try_collect()
+        .await
+    }
+
     /// Set association labels between two records.
     pub async fn create<O>(
         &self,
@@ -166,4 +503,841 @@ where
             ))
             .await
     }
+
+    /// Create the default, unlabeled association between two records.
+    ///
+    /// Returns the associations created, one for each direction.
+    pub async fn create_default<O>(
+        &self,
+        id: &str,
+        to_object_type: O,
+        to_object_id: &str,
+    ) -> HubspotResult<Vec<DefaultAssociation>>
+    where
+        O: ToPath + Send,
+    {
+        self.client()
+            .send::<ListResult<DefaultAssociation>>(self.client().begin(
+                Method::PUT,
+                &format!(
+                    "crm/v4/objects/{}/{}/associations/default/{}/{}",
+                    self.path(),
+                    id,
+                    to_object_type.to_path(),
+                    to_object_id
+                ),
+            ))
+            .await
+            .map(|associations| associations.results)
+    }
+
+    /// Associate a batch of record pairs with the given association types.
+    ///
+    /// Pairs are chunked and failed pairs handled as in the object batch api,
+    /// with the errors of the failed pairs in [AssociationBatchResult::errors].
+    pub async fn batch_create<O>(
+        &self,
+        to_object_type: O,
+        pairs: Vec<AssociationPair>,
+        options: BatchOptions,
+    ) -> HubspotResult<AssociationBatchResult<CreatedAssociationResult>>
+    where
+        O: ToPath,
+    {
+        let path = format!(
+            "crm/v4/associations/{}/{}/batch/create",
+            self.path(),
+            to_object_type.to_path()
+        );
+
+        send_batch(
+            pairs,
+            options,
+            |pair| Some(&pair.from.id),
+            |inputs| {
+                self.client()
+                    .send::<AssociationBatchResult<CreatedAssociationResult>>(
+                        self.client()
+                            .begin(Method::POST, &path)
+                            .json(&AssociationBatchInputs { inputs }),
+                    )
+            },
+        )
+        .await
+    }
+
+    /// Create the default, unlabeled association between a batch of record pairs, given as `(from_id, to_id)`.
+    pub async fn batch_create_default<O>(
+        &self,
+        to_object_type: O,
+        pairs: Vec<(&str, &str)>,
+        options: BatchOptions,
+    ) -> HubspotResult<AssociationBatchResult<DefaultAssociation>>
+    where
+        O: ToPath,
+    {
+        let path = format!(
+            "crm/v4/associations/{}/{}/batch/associate/default",
+            self.path(),
+            to_object_type.to_path()
+        );
+        let inputs = pairs
+            .into_iter()
+            .map(|(from_id, to_id)| AssociationDefaultInput {
+                from: AssociationObjectId {
+                    id: from_id.to_owned(),
+                },
+                to: AssociationObjectId {
+                    id: to_id.to_owned(),
+                },
+            })
+            .collect();
+
+        send_batch(
+            inputs,
+            options,
+            |input| Some(&input.from.id),
+            |inputs| {
+                self.client()
+                    .send::<AssociationBatchResult<DefaultAssociation>>(
+                        self.client()
+                            .begin(Method::POST, &path)
+                            .json(&AssociationBatchInputs { inputs }),
+                    )
+            },
+        )
+        .await
+    }
+
+    /// Read the associations of a batch of records to an object type.
+    ///
+    /// Each record returns its first page of associations; use [AssociationsApiCollection::list_all]
+    /// for the records whose `paging` has more.
+    pub async fn batch_read<O>(
+        &self,
+        to_object_type: O,
+        ids: Vec<&str>,
+        options: BatchOptions,
+    ) -> HubspotResult<AssociationBatchResult<RecordAssociations>>
+    where
+        O: ToPath,
+    {
+        let path = format!(
+            "crm/v4/associations/{}/{}/batch/read",
+            self.path(),
+            to_object_type.to_path()
+        );
+        let inputs = ids
+            .into_iter()
+            .map(|id| AssociationReadInput { id: id.to_owned() })
+            .collect();
+
+        send_batch(
+            inputs,
+            options,
+            |input| Some(&input.id),
+            |inputs| {
+                self.client()
+                    .send::<AssociationBatchResult<RecordAssociations>>(
+                        self.client()
+                            .begin(Method::POST, &path)
+                            .json(&AssociationBatchInputs { inputs }),
+                    )
+            },
+        )
+        .await
+    }
+
+    /// Remove every association between a batch of record pairs, given as `(from_id, to_id)`.
+    pub async fn batch_archive<O>(
+        &self,
+        to_object_type: O,
+        pairs: Vec<(&str, &str)>,
+        options: BatchOptions,
+    ) -> HubspotResult<AssociationBatchResult<OptionNotDesired>>
+    where
+        O: ToPath,
+    {
+        let path = format!(
+            "crm/v4/associations/{}/{}/batch/archive",
+            self.path(),
+            to_object_type.to_path()
+        );
+        let inputs = pairs
+            .into_iter()
+            .map(|(from_id, to_id)| AssociationArchiveInput {
+                from: AssociationObjectId {
+                    id: from_id.to_owned(),
+                },
+                to: vec![AssociationObjectId {
+                    id: to_id.to_owned(),
+                }],
+            })
+            .collect();
+
+        send_batch(
+            inputs,
+            options,
+            |input| Some(&input.from.id),
+            |inputs| {
+                self.client()
+                    .send::<Option<AssociationBatchResult<OptionNotDesired>>>(
+                        self.client()
+                            .begin(Method::POST, &path)
+                            .json(&AssociationBatchInputs { inputs }),
+                    )
+                    .map(|result| result.map(Option::unwrap_or_default))
+            },
+        )
+        .await
+    }
+
+    /// Remove the given association types from a batch of record pairs, keeping their other associations.
+    pub async fn batch_archive_labels<O>(
+        &self,
+        to_object_type: O,
+        pairs: Vec<AssociationPair>,
+        options: BatchOptions,
+    ) -> HubspotResult<AssociationBatchResult<OptionNotDesired>>
+    where
+        O: ToPath,
+    {
+        let path = format!(
+            "crm/v4/associations/{}/{}/batch/labels/archive",
+            self.path(),
+            to_object_type.to_path()
+        );
+
+        send_batch(
+            pairs,
+            options,
+            |pair| Some(&pair.from.id),
+            |inputs| {
+                self.client()
+                    .send::<Option<AssociationBatchResult<OptionNotDesired>>>(
+                        self.client()
+                            .begin(Method::POST, &path)
+                            .json(&AssociationBatchInputs { inputs }),
+                    )
+                    .map(|result| result.map(Option::unwrap_or_default))
+            },
+        )
+        .await
+    }
+
+    /// Returns the association types between the collection's object type and another,
+    /// including the user defined labels.
+    pub async fn labels<O>(&self, to_object_type: O) -> HubspotResult<Vec<AssociationTypes>>
+    where
+        O: ToPath,
+    {
+        self.labels_for(&to_object_type.to_path()).await
+    }
+
+    async fn labels_for(&self, to_path: &str) -> HubspotResult<Vec<AssociationTypes>> {
+        if let Some(labels) = self.2.read().unwrap().get(to_path) {
+            return Ok(labels.clone());
+        }
+
+        let labels = self
+            .client()
+            .send::<ListResult<AssociationTypes>>(self.client().begin(
+                Method::GET,
+                &format!("crm/v4/associations/{}/{}/labels", self.path(), to_path),
+            ))
+            .await?
+            .results;
+        self.2
+            .write()
+            .unwrap()
+            .insert(to_path.to_owned(), labels.clone());
+        Ok(labels)
+    }
+
+    /// Resolve an association label such as "Decision maker" to its category and type ID.
+    ///
+    /// Labels are matched ignoring ASCII case, and cached after the first lookup.
+    /// A label missing from the cache is looked up again, in case it was added since.
+    pub async fn resolve_label<O>(
+        &self,
+        to_object_type: O,
+        label: &str,
+    ) -> HubspotResult<AssociationCreationDetails>
+    where
+        O: ToPath,
+    {
+        self.resolve_label_for(&to_object_type.to_path(), label)
+            .await
+    }
+
+    async fn resolve_label_for(
+        &self,
+        to_path: &str,
+        label: &str,
+    ) -> HubspotResult<AssociationCreationDetails> {
+        if let Some(details) = find_label(&self.labels_for(to_path).await?, label) {
+            return Ok(details);
+        }
+
+        // The label may have been added since the labels were cached, so fetch them once more.
+        self.2.write().unwrap().remove(to_path);
+        find_label(&self.labels_for(to_path).await?, label).ok_or_else(|| {
+            HubspotError::Association(format!(
+                "No association label {:?} from {} to {}",
+                label,
+                self.path(),
+                to_path
+            ))
+        })
+    }
+
+    /// Define a new association label, returning the association types created for it.
+    pub async fn create_label<O>(
+        &self,
+        to_object_type: O,
+        label: &str,
+        name: &str,
+        inverse_label: Option<&str>,
+    ) -> HubspotResult<Vec<AssociationTypes>>
+    where
+        O: ToPath,
+    {
+        let to_path = to_object_type.to_path();
+        let result = self
+            .client()
+            .send::<ListResult<AssociationTypes>>(
+                self.client()
+                    .begin(
+                        Method::POST,
+                        &format!("crm/v4/associations/{}/{}/labels", self.path(), to_path),
+                    )
+                    .json(&AssociationLabelInput {
+                        label,
+                        name,
+                        inverse_label,
+                    }),
+            )
+            .await
+            .map(|labels| labels.results);
+        self.2.write().unwrap().remove(&to_path);
+        result
+    }
+
+    /// Rename an association label.
+    pub async fn update_label<O>(
+        &self,
+        to_object_type: O,
+        type_id: i64,
+        label: &str,
+        inverse_label: Option<&str>,
+    ) -> HubspotResult<()>
+    where
+        O: ToPath,
+    {
+        let to_path = to_object_type.to_path();
+        let result = self
+            .client()
+            .send(
+                self.client()
+                    .begin(
+                        Method::PUT,
+                        &format!("crm/v4/associations/{}/{}/labels", self.path(), to_path),
+                    )
+                    .json(&AssociationLabelUpdate {
+                        association_type_id: type_id,
+                        label,
+                        inverse_label,
+                    }),
+            )
+            .await;
+        self.2.write().unwrap().remove(&to_path);
+        result
+    }
+
+    /// Delete an association label.
+    pub async fn delete_label<O>(&self, to_object_type: O, type_id: i64) -> HubspotResult<()>
+    where
+        O: ToPath,
+    {
+        let to_path = to_object_type.to_path();
+        let result = self
+            .client()
+            .send(self.client().begin(
+                Method::DELETE,
+                &format!(
+                    "crm/v4/associations/{}/{}/labels/{}",
+                    self.path(),
+                    to_path,
+                    type_id
+                ),
+            ))
+            .await;
+        self.2.write().unwrap().remove(&to_path);
+        result
+    }
+
+    /// Set association labels between two records by label name, eg. "Decision maker".
+    pub async fn create_with_labels<O>(
+        &self,
+        id: &str,
+        to_object_type: O,
+        to_object_id: &str,
+        labels: Vec<&str>,
+    ) -> HubspotResult<CreatedAssociationResult>
+    where
+        O: ToPath + Send,
+    {
+        let to_path = to_object_type.to_path();
+        let mut associations_to_create = Vec::with_capacity(labels.len());
+        for label in labels {
+            associations_to_create.push(self.resolve_label_for(&to_path, label).await?);
+        }
+
+        self.create(id, to_object_type, to_object_id, associations_to_create)
+            .await
+    }
+
+    /// The primary company association type of the collection's object type.
+    fn primary_company_link(&self) -> HubspotResult<AssociationLinks> {
+        let path = self.path();
+        AssociationLinks::iter()
+            .find(|link| {
+                link.is_primary()
+                    && link.source_path() == path
+                    && link.target_path() == ObjectType::Companies.to_path()
+            })
+            .ok_or_else(|| HubspotError::Association(format!("{} have no primary company", path)))
+    }
+
+    /// Returns the ID of a record's primary company, if it has one.
+    ///
+    /// Only contacts, deals and tickets have a primary company.
+    pub async fn primary_company(&self, id: &str) -> HubspotResult<Option<String>> {
+        let primary = self.primary_company_link()?.type_id();
+
+        let mut associations = pin!(self.list_all(
+            id,
+            &ObjectType::Companies.to_path(),
+            PagingOptions::new().page_size(500),
+        ));
+        while let Some(association) = associations.try_next().await? {
+            if association
+                .association_types
+                .iter()
+                .any(|association_type| association_type.type_id == primary)
+            {
+                return Ok(Some(association.to_object_id.to_string()));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Make a company the primary company of a record.
+    ///
+    /// The previous primary company, if the record had one, stays associated with the record
+    /// without the primary label. When removing the label fails after the new primary company
+    /// was set, the error is reported in [PrimaryCompanyChange::cleanup_error] rather than returned.
+    pub async fn set_primary_company(
+        &self,
+        id: &str,
+        company_id: &str,
+    ) -> HubspotResult<PrimaryCompanyChange> {
+        let primary = AssociationCreationDetails::from(&self.primary_company_link()?);
+        let previous = self.primary_company(id).await?;
+
+        self.create(id, ObjectType::Companies, company_id, vec![primary.clone()])
+            .await?;
+
+        let mut change = PrimaryCompanyChange {
+            previous,
+            cleanup_error: None,
+        };
+        if let Some(previous_id) = change.replaced(company_id) {
+            change.cleanup_error = self
+                .batch_archive_labels(
+                    ObjectType::Companies,
+                    vec![AssociationPair::new(id, previous_id, vec![primary])],
+                    BatchOptions::new().error_policy(BatchErrorPolicy::FailOnError),
+                )
+                .await
+                .err();
+        }
+
+        Ok(change)
+    }
+
+    /// Replace the primary company of a record, removing every association to the previous primary company.
+    ///
+    /// When deleting the previous association fails after the new primary company was set,
+    /// the error is reported in [PrimaryCompanyChange::cleanup_error] rather than returned.
+    pub async fn swap_primary_company(
+        &self,
+        id: &str,
+        company_id: &str,
+    ) -> HubspotResult<PrimaryCompanyChange> {
+        let mut change = self.set_primary_company(id, company_id).await?;
+
+        if let Some(previous_id) = change.replaced(company_id) {
+            // Deleting the association also removes a primary label left by a failed cleanup.
+            change.cleanup_error = self
+                .delete(id, ObjectType::Companies, previous_id)
+                .await
+                .err();
+        }
+
+        Ok(change)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::StatusCode;
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::client::transport::{HttpResponse, MockTransport};
+    use crate::Hubspot;
+
+    fn hubspot(transport: &Arc<MockTransport>) -> Hubspot {
+        Hubspot::builder()
+            .domain("api.hubapi.com")
+            .token("token")
+            .portal_id("1")
+            .transport(Arc::clone(transport) as Arc<_>)
+            .build()
+            .unwrap()
+    }
+
+    fn labels(labels: &[(i64, &str)]) -> Value {
+        let results = labels
+            .iter()
+            .map(|(type_id, label)| json!({ "category": "USER_DEFINED", "typeId": type_id, "label": label }))
+            .collect::<Vec<_>>();
+        json!({ "results": results })
+    }
+
+    #[tokio::test]
+    async fn resolves_labels_ignoring_case_from_the_cache() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond_json(StatusCode::OK, &labels(&[(5, "Decision maker")]));
+        let associations = hubspot(&transport).objects.contacts.associations;
+
+        let details = associations
+            .resolve_label(ObjectType::Companies, "decision MAKER")
+            .await
+            .unwrap();
+        associations
+            .resolve_label(ObjectType::Companies, "Decision maker")
+            .await
+            .unwrap();
+
+        assert_eq!(details.category, "USER_DEFINED");
+        assert_eq!(details.type_id, 5);
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(
+            requests[0].url.path(),
+            "/crm/v4/associations/contacts/companies/labels"
+        );
+    }
+
+    #[tokio::test]
+    async fn refetches_the_labels_when_a_label_is_not_cached() {
+        let transport = Arc::new(MockTransport::new());
+        transport
+            .respond_json(StatusCode::OK, &labels(&[(5, "Decision maker")]))
+            .respond_json(
+                StatusCode::OK,
+                &labels(&[(5, "Decision maker"), (6, "Billing contact")]),
+            )
+            .respond_json(
+                StatusCode::OK,
+                &labels(&[(5, "Decision maker"), (6, "Billing contact")]),
+            );
+        let associations = hubspot(&transport).objects.contacts.associations;
+
+        associations
+            .resolve_label(ObjectType::Companies, "Decision maker")
+            .await
+            .unwrap();
+        let details = associations
+            .resolve_label(ObjectType::Companies, "Billing contact")
+            .await
+            .unwrap();
+        let error = associations
+            .resolve_label(ObjectType::Companies, "Champion")
+            .await
+            .unwrap_err();
+
+        assert_eq!(details.type_id, 6);
+        assert!(matches!(error, HubspotError::Association(_)));
+        assert_eq!(transport.requests().len(), 3);
+    }
+
+    fn companies(primary_id: Option<i64>) -> Value {
+        let results = primary_id
+            .map(|id| {
+                json!({
+                    "toObjectId": id,
+                    "associationTypes": [
+                        { "category": "HUBSPOT_DEFINED", "typeId": 1, "label": "Primary" },
+                        { "category": "HUBSPOT_DEFINED", "typeId": 279, "label": null },
+                    ]
+                })
+            })
+            .into_iter()
+            .collect::<Vec<_>>();
+        json!({ "results": results })
+    }
+
+    fn created(to_object_id: i64) -> Value {
+        json!({ "fromObjectTypeId": "0-1", "fromObjectId": 1, "toObjectId": to_object_id, "labels": ["Primary"] })
+    }
+
+    #[tokio::test]
+    async fn moves_the_primary_label_from_the_previous_company() {
+        let transport = Arc::new(MockTransport::new());
+        transport
+            .respond_json(StatusCode::OK, &companies(Some(7)))
+            .respond_json(StatusCode::OK, &created(8))
+            .respond(HttpResponse::new(StatusCode::NO_CONTENT, Vec::new()));
+
+        let change = hubspot(&transport)
+            .objects
+            .contacts
+            .associations
+            .set_primary_company("1", "8")
+            .await
+            .unwrap();
+
+        assert_eq!(change.previous.as_deref(), Some("7"));
+        assert!(change.cleanup_error.is_none());
+        let requests = transport.requests();
+        assert_eq!(requests[1].method, Method::PUT);
+        assert_eq!(
+            requests[2].url.path(),
+            "/crm/v4/associations/contacts/companies/batch/labels/archive"
+        );
+    }
+
+    #[tokio::test]
+    async fn only_cleans_up_a_previous_primary_company() {
+        let transport = Arc::new(MockTransport::new());
+        transport
+            .respond_json(StatusCode::OK, &companies(None))
+            .respond_json(StatusCode::OK, &created(8));
+
+        let change = hubspot(&transport)
+            .objects
+            .contacts
+            .associations
+            .swap_primary_company("1", "8")
+            .await
+            .unwrap();
+
+        assert!(change.previous.is_none());
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn reports_a_failed_cleanup_once_the_primary_company_is_set() {
+        let transport = Arc::new(MockTransport::new());
+        transport
+            .respond_json(StatusCode::OK, &companies(Some(7)))
+            .respond_json(StatusCode::OK, &created(8))
+            .respond_json(
+                StatusCode::BAD_REQUEST,
+                &json!({ "message": "Invalid input", "category": "VALIDATION_ERROR" }),
+            );
+
+        let change = hubspot(&transport)
+            .objects
+            .contacts
+            .associations
+            .set_primary_company("1", "8")
+            .await
+            .unwrap();
+
+        assert_eq!(change.previous.as_deref(), Some("7"));
+        assert_eq!(
+            change.cleanup_error.unwrap().status(),
+            Some(StatusCode::BAD_REQUEST)
+        );
+    }
+
+    #[tokio::test]
+    async fn swapping_deletes_the_previous_primary_company() {
+        let transport = Arc::new(MockTransport::new());
+        transport
+            .respond_json(StatusCode::OK, &companies(Some(7)))
+            .respond_json(StatusCode::OK, &created(8))
+            .respond_json(
+                StatusCode::BAD_REQUEST,
+                &json!({ "message": "Invalid input", "category": "VALIDATION_ERROR" }),
+            )
+            .respond(HttpResponse::new(StatusCode::NO_CONTENT, Vec::new()));
+
+        let change = hubspot(&transport)
+            .objects
+            .contacts
+            .associations
+            .swap_primary_company("1", "8")
+            .await
+            .unwrap();
+
+        assert_eq!(change.previous.as_deref(), Some("7"));
+        assert!(change.cleanup_error.is_none());
+        let requests = transport.requests();
+        assert_eq!(requests[3].method, Method::DELETE);
+        assert_eq!(
+            requests[3].url.path(),
+            "/crm/v4/objects/contacts/1/associations/companies/7"
+        );
+    }
+
+    fn read_with_record(associations: Value) -> AssociationResults {
+        serde_json::from_value(associations).unwrap()
+    }
+
+    #[tokio::test]
+    async fn groups_complete_associations_without_a_request() {
+        let transport = Arc::new(MockTransport::new());
+        let associations = read_with_record(json!({
+            "results": [
+                { "id": "7", "type": "contact_to_company" },
+                { "id": "7", "type": "contact_to_company_unlabeled" },
+                { "id": "8", "type": "contact_to_company_unlabeled" },
+            ]
+        }));
+
+        let complete = hubspot(&transport)
+            .objects
+            .contacts
+            .associations
+            .complete("1", ObjectType::Companies, &associations)
+            .await
+            .unwrap();
+
+        assert!(transport.requests().is_empty());
+        assert_eq!(complete.len(), 2);
+        assert_eq!(complete[0].to_object_id, 7);
+        let types = &complete[0].association_types;
+        assert_eq!(types.len(), 2);
+        assert_eq!(
+            (types[0].type_id, types[0].label.as_deref()),
+            (1, Some("Primary"))
+        );
+        assert_eq!((types[1].type_id, types[1].label.as_deref()), (279, None));
+        assert_eq!(complete[1].to_object_id, 8);
+    }
+
+    #[tokio::test]
+    async fn reads_every_page_of_paged_associations() {
+        let transport = Arc::new(MockTransport::new());
+        transport
+            .respond_json(
+                StatusCode::OK,
+                &json!({
+                    "results": [{ "toObjectId": 7, "associationTypes": [
+                        { "category": "HUBSPOT_DEFINED", "typeId": 279, "label": null },
+                    ] }],
+                    "paging": { "next": { "after": "7" } }
+                }),
+            )
+            .respond_json(
+                StatusCode::OK,
+                &json!({
+                    "results": [{ "toObjectId": 8, "associationTypes": [
+                        { "category": "USER_DEFINED", "typeId": 5, "label": "Decision maker" },
+                    ] }]
+                }),
+            );
+        let associations = read_with_record(json!({
+            "results": [{ "id": "7", "type": "contact_to_company_unlabeled" }],
+            "paging": { "next": { "after": "7" } }
+        }));
+
+        let complete = hubspot(&transport)
+            .objects
+            .contacts
+            .associations
+            .complete("1", ObjectType::Companies, &associations)
+            .await
+            .unwrap();
+
+        let ids = complete
+            .iter()
+            .map(|associated| associated.to_object_id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![7, 8]);
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[0].url.path(),
+            "/crm/v4/objects/contacts/1/associations/companies"
+        );
+        assert_eq!(requests[1].url.query(), Some("limit=500&after=7"));
+    }
+
+    #[tokio::test]
+    async fn reads_associations_of_types_it_cannot_group() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond_json(
+            StatusCode::OK,
+            &json!({
+                "results": [{ "toObjectId": 7, "associationTypes": [
+                    { "category": "USER_DEFINED", "typeId": 5, "label": "Decision maker" },
+                ] }]
+            }),
+        );
+        let associations = read_with_record(json!({
+            "results": [{ "id": "7", "type": "5" }]
+        }));
+
+        let complete = hubspot(&transport)
+            .objects
+            .contacts
+            .associations
+            .complete("1", ObjectType::Companies, &associations)
+            .await
+            .unwrap();
+
+        assert_eq!(transport.requests().len(), 1);
+        assert_eq!(
+            complete[0].association_types[0].label.as_deref(),
+            Some("Decision maker")
+        );
+    }
+
+    #[test]
+    fn merges_the_chunks_of_a_batch() {
+        let chunk = |status: &str, at: &str, results: Vec<u32>| AssociationBatchResult {
+            status: status.to_owned(),
+            results,
+            requested_at: at.to_owned(),
+            started_at: at.to_owned(),
+            completed_at: at.to_owned(),
+            ..Default::default()
+        };
+        let mut failed = AssociationBatchResult::merge(Vec::new());
+        failed.errors.push(BatchError::from_failed_request(
+            &HubspotError::UnexpectedResponse("failed".to_owned()),
+            vec!["3".to_owned()],
+            vec![2],
+        ));
+
+        let merged = AssociationBatchResult::merge(vec![
+            chunk("COMPLETE", "2024-01-01T00:00:02Z", vec![1]),
+            failed,
+            chunk("PROCESSING", "2024-01-01T00:00:01Z", vec![2]),
+        ]);
+
+        assert_eq!(merged.status, "PROCESSING");
+        assert_eq!(merged.results, vec![1, 2]);
+        assert_eq!(merged.requested_at, "2024-01-01T00:00:01Z");
+        assert_eq!(merged.completed_at, "2024-01-01T00:00:02Z");
+        assert_eq!(merged.num_errors, 1);
+        assert_eq!(merged.errors[0].ids(), ["3"]);
+    }
 }
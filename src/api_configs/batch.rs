@@ -1,54 +1,371 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    mem,
+    sync::Arc,
+    time::Duration,
+};
 
+use futures::{stream, FutureExt, StreamExt};
 use reqwest::Method;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_aux::serde_introspection::serde_introspect;
+use serde_json::Value;
 
-use crate::client::{error::HubspotResult, HubspotClient};
+use crate::client::{
+    error::{BatchError, HubspotError, HubspotResult},
+    retry::RetryPolicy,
+    HubspotClient,
+};
 
-use super::types::{HubspotRecord, ObjectApi, OptionNotDesired, ToPath};
+use super::query::Query;
+use super::types::{CreateAssociation, HubspotRecord, ObjectApi, OptionNotDesired, ToPath};
+
+/// The most inputs Hubspot accepts in a single batch request.
+pub const MAX_BATCH_SIZE: usize = 100;
+
+/// What a batch call does when Hubspot reports errors for some of its inputs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BatchErrorPolicy {
+    /// Return [HubspotError::Batch] if any input failed, or the error of a request that failed as a whole.
+    FailOnError,
+    /// Return the results of the successful inputs, with the errors in [BatchResult::errors].
+    ///
+    /// A request that fails as a whole is reported as an error for each of its inputs,
+    /// unless no request of the call succeeded, which returns the error of the request.
+    #[default]
+    ReturnPartial,
+    /// Resend only the inputs that failed with a transient error, being rate limited or a server error,
+    /// up to `max_retries` times, then return the partial results.
+    ///
+    /// Each round waits for the retry delay of the options, doubling with every round.
+    /// The inputs of a request that failed as a whole are resent by their position. Otherwise
+    /// inputs are matched to errors by the IDs in the errors' context, so records to create
+    /// that Hubspot rejects individually are not retried.
+    RetryFailed {
+        /// The most times the failed inputs are resent.
+        max_retries: u32,
+    },
+}
+
+/// Options for splitting batch inputs into multiple requests and handling failed inputs.
+#[derive(Clone, Copy, Debug)]
+pub struct BatchOptions {
+    chunk_size: usize,
+    concurrency: usize,
+    error_policy: BatchErrorPolicy,
+    retry_delay: Duration,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            chunk_size: MAX_BATCH_SIZE,
+            concurrency: 1,
+            error_policy: BatchErrorPolicy::default(),
+            retry_delay: Duration::from_millis(500),
+        }
+    }
+}
+
+impl BatchOptions {
+    /// Create batch options sending chunks of 100 inputs, one request at a time.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The number of inputs to send per request, at most 100.
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.clamp(1, MAX_BATCH_SIZE);
+        self
+    }
+
+    /// The number of requests to send at the same time.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// What to do when some inputs fail. Defaults to [BatchErrorPolicy::ReturnPartial].
+    pub fn error_policy(mut self, error_policy: BatchErrorPolicy) -> Self {
+        self.error_policy = error_policy;
+        self
+    }
+
+    /// The delay before the first resend of [BatchErrorPolicy::RetryFailed]. Doubles with every following round.
+    pub fn retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+}
+
+/// The response of a batch request, which can be merged across chunks.
+pub(crate) trait BatchResponse: Sized {
+    /// Merge the responses of the chunks of a batch request into one response.
+    fn merge(chunks: Vec<Self>) -> Self;
+
+    /// The errors of the failed inputs.
+    fn errors_mut(&mut self) -> &mut Vec<BatchError>;
+}
+
+/// Send the inputs in chunks of the configured size, returning the result of each chunk in chunk order.
+///
+/// Every chunk is sent, even when an earlier one fails.
+pub(crate) async fn send_chunked<I, R, F, Fut>(
+    inputs: &[I],
+    options: BatchOptions,
+    send: F,
+) -> Vec<HubspotResult<R>>
+where
+    F: FnMut(&[I]) -> Fut,
+    Fut: Future<Output = HubspotResult<R>>,
+{
+    stream::iter(inputs.chunks(options.chunk_size).map(send))
+        .buffered(options.concurrency)
+        .collect()
+        .await
+}
+
+/// Send the inputs in chunks, handling failed inputs with the error policy of the options.
+///
+/// A chunk whose request fails as a whole is reported as a [BatchError] for each of its inputs,
+/// keeping the results of the other chunks, unless the policy is [BatchErrorPolicy::FailOnError].
+/// When no request of the call succeeds there are no results to keep, so the error of the last
+/// failed request is returned whatever the policy.
+/// `input_id` returns the ID Hubspot reports in the errors of an input, if it has one.
+pub(crate) async fn send_batch<I, R, F, Fut>(
+    inputs: Vec<I>,
+    options: BatchOptions,
+    input_id: fn(&I) -> Option<&str>,
+    mut send: F,
+) -> HubspotResult<R>
+where
+    R: BatchResponse,
+    F: FnMut(&[I]) -> Fut,
+    Fut: Future<Output = HubspotResult<R>>,
+{
+    // The position of each pending input in the call, reported in the errors of failed requests.
+    let mut positions = (0..inputs.len()).collect::<Vec<_>>();
+    let mut pending = inputs;
+    let mut responses = Vec::new();
+    let mut retries = 0;
+    let mut succeeded = false;
+    let mut request_error = None;
+
+    loop {
+        let mut chunk_responses = Vec::new();
+        let results = send_chunked(&pending, options, &mut send).await;
+        let chunks = pending
+            .chunks(options.chunk_size)
+            .zip(positions.chunks(options.chunk_size));
+        for ((chunk, chunk_positions), result) in chunks.zip(results) {
+            match result {
+                Ok(response) => {
+                    succeeded = true;
+                    chunk_responses.push(response);
+                }
+                Err(error) if options.error_policy == BatchErrorPolicy::FailOnError => {
+                    return Err(error)
+                }
+                Err(error) => {
+                    let ids = chunk
+                        .iter()
+                        .filter_map(input_id)
+                        .map(str::to_owned)
+                        .collect();
+                    let mut response = R::merge(Vec::new());
+                    response.errors_mut().push(BatchError::from_failed_request(
+                        &error,
+                        ids,
+                        chunk_positions.to_vec(),
+                    ));
+                    chunk_responses.push(response);
+                    request_error = Some(error);
+                }
+            }
+        }
+        let mut response = R::merge(chunk_responses);
+
+        let max_retries = match options.error_policy {
+            BatchErrorPolicy::RetryFailed { max_retries } => max_retries,
+            _ => 0,
+        };
+        if retries >= max_retries || response.errors_mut().is_empty() {
+            responses.push(response);
+            break;
+        }
+
+        // Inputs of a request that failed as a whole are resent by position, others by their ID.
+        let transient = response
+            .errors_mut()
+            .iter()
+            .filter(|error| error.is_transient());
+        let failed_positions = transient
+            .clone()
+            .flat_map(|error| error.input_indexes.iter().copied())
+            .collect::<HashSet<_>>();
+        let failed_ids = transient
+            .flat_map(|error| error.ids())
+            .cloned()
+            .collect::<HashSet<_>>();
+        let (failed, failed_positions): (Vec<I>, Vec<usize>) = pending
+            .into_iter()
+            .zip(positions)
+            .filter(|(input, position)| {
+                failed_positions.contains(position)
+                    || input_id(input).is_some_and(|id| failed_ids.contains(id))
+            })
+            .unzip();
+        if failed.is_empty() {
+            responses.push(response);
+            break;
+        }
+
+        // Drop the errors of the inputs being resent; the retry reports them again if they still fail.
+        let retried_positions = failed_positions.iter().copied().collect::<HashSet<_>>();
+        let retried_ids = failed
+            .iter()
+            .filter_map(|input| input_id(input))
+            .map(str::to_owned)
+            .collect::<HashSet<_>>();
+        response.errors_mut().retain(|error| {
+            !(error
+                .input_indexes
+                .iter()
+                .any(|position| retried_positions.contains(position))
+                || error.ids().iter().any(|id| retried_ids.contains(id)))
+        });
+        responses.push(response);
+        pending = failed;
+        positions = failed_positions;
+        retries += 1;
+        tokio::time::sleep(
+            RetryPolicy::new()
+                .base_delay(options.retry_delay)
+                .backoff(retries),
+        )
+        .await;
+    }
+
+    if let (false, Some(error)) = (succeeded, request_error) {
+        return Err(error);
+    }
+
+    let mut response = R::merge(responses);
+    if options.error_policy == BatchErrorPolicy::FailOnError && !response.errors_mut().is_empty() {
+        return Err(HubspotError::Batch {
+            errors: mem::take(response.errors_mut()),
+        });
+    }
+    Ok(response)
+}
+
+/// The status and timestamps of a batch response, merged across the responses of its chunks.
+#[derive(Debug)]
+pub(crate) struct BatchStatus {
+    pub(crate) status: String,
+    pub(crate) requested_at: String,
+    pub(crate) started_at: String,
+    pub(crate) completed_at: String,
+}
+
+impl Default for BatchStatus {
+    fn default() -> Self {
+        Self {
+            status: "COMPLETE".to_owned(),
+            requested_at: String::new(),
+            started_at: String::new(),
+            completed_at: String::new(),
+        }
+    }
+}
+
+impl BatchStatus {
+    /// Merge the status and timestamps of a chunk's response.
+    ///
+    /// The first status other than `COMPLETE` wins. Timestamps are RFC 3339 in UTC, so they
+    /// order as strings; empty ones, as in the response made up for a failed request, are skipped.
+    pub(crate) fn merge(
+        &mut self,
+        status: String,
+        requested_at: String,
+        started_at: String,
+        completed_at: String,
+    ) {
+        if self.status == "COMPLETE" && !status.is_empty() {
+            self.status = status;
+        }
+        if !requested_at.is_empty()
+            && (self.requested_at.is_empty() || requested_at < self.requested_at)
+        {
+            self.requested_at = requested_at;
+        }
+        if !started_at.is_empty() && (self.started_at.is_empty() || started_at < self.started_at) {
+            self.started_at = started_at;
+        }
+        if completed_at > self.completed_at {
+            self.completed_at = completed_at;
+        }
+    }
+}
 
 /// A wrapper type for batch inputs.
 #[derive(Serialize, Debug)]
-struct BatchInputs<I> {
+struct BatchInputs<'a, I> {
     /// The inputs for a Batch request.
-    inputs: Vec<I>,
+    inputs: &'a [I],
 }
 
-impl<I> BatchInputs<I> {
+impl<'a, I> BatchInputs<'a, I> {
     /// Constructs a new BatchInput
-    pub fn new(inputs: Vec<I>) -> BatchInputs<I> {
+    pub fn new(inputs: &'a [I]) -> BatchInputs<'a, I> {
         BatchInputs { inputs }
     }
 }
 
-/// A struct of record Ids for the update batch api.
-/// eg. Batch update.
+/// A record to update for the batch update api.
 #[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
 struct BatchUpdateInput<Properties> {
-    /// Hubspot record Ids for a batch request.
+    /// The record's ID, or the value of its unique property.
     id: String,
+    /// The unique property the record is identified by, when not its ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id_property: Option<String>,
     /// The property inputs for a batch request
     properties: Properties,
 }
 
-impl<Properties: Clone> BatchUpdateInput<Properties> {
-    /// Constructs a new BatchUpdateInput
-    pub fn new(id: &str, properties: Properties) -> BatchUpdateInput<Properties> {
-        BatchUpdateInput {
-            id: id.to_string(),
-            properties,
-        }
-    }
+/// How the record of a batch update input is identified in the update.
+#[derive(Debug)]
+enum UpdateTarget {
+    /// Updated by its record ID.
+    Id(String),
+    /// Updated by the key of its unique property's value.
+    Property(String),
+}
 
-    /// Constructs a new vec of BatchUpdateInputs from a list of record IDs.
-    pub fn new_batch(
-        ids: Vec<String>,
-        properties: Properties,
-    ) -> Vec<BatchUpdateInput<Properties>> {
-        ids.iter()
-            .map(|id| BatchUpdateInput::new(id, properties.clone()))
-            .collect()
-    }
+/// The result of a batch update for one of the inputs.
+#[derive(Debug)]
+pub struct BatchUpdateOutcome<Properties> {
+    /// The ID or unique property value of the input, as given.
+    pub input_id: String,
+    /// The updated record, or `None` if the input failed or no record has its unique property value.
+    pub record: Option<HubspotRecord<Properties, OptionNotDesired, OptionNotDesired>>,
+    /// The error Hubspot reported for the input, if it failed.
+    pub error: Option<BatchError>,
+}
+
+/// A record to create or update by a unique property for the batch upsert api.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct BatchUpsertInput<Properties> {
+    /// The unique property the record is identified by.
+    id_property: String,
+    /// The value of the unique property.
+    id: String,
+    /// The property inputs for a batch request
+    properties: Properties,
 }
 
 /// A struct of record Ids for the batch api.
@@ -59,28 +376,29 @@ struct BatchIdInput {
     id: String,
 }
 
-/// A wrapper type for batch properties inputs
+/// A record to create for the batch create api.
 #[derive(Serialize, Debug)]
-struct BatchPropertiesInput<Properties> {
+struct BatchCreateInput<Properties> {
     /// The property inputs for a batch request
     properties: Properties,
+    /// The records to associate the new record with.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    associations: Vec<CreateAssociation>,
 }
 
 /// The required inputs for a Batch Read request.
 #[derive(Serialize, Debug)]
-struct BatchReadInputs<Properties, PropertiesWithHistory, Associations> {
+#[serde(rename_all = "camelCase")]
+struct BatchReadInputs<'a> {
     /// The record ids to return for a batch request.
-    inputs: Vec<BatchIdInput>,
-    /// The record properties for a batch request
-    properties: Properties,
-    /// The record properties with history for a batch request
-    #[serde(alias = "propertiesWithHistory")]
-    #[serde(default)]
-    properties_with_history: PropertiesWithHistory,
-    /// The record associations for a batch request
-    associations: Associations,
-    /// Whether to return only results that have been archived.
-    archived: bool,
+    inputs: &'a [BatchIdInput],
+    /// The names of the record properties to return.
+    properties: &'a [String],
+    /// The names of the record properties to return with their history.
+    properties_with_history: &'a [String],
+    /// The unique property the records are identified by, when not their IDs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id_property: Option<&'a str>,
 }
 
 /// A Hubspot result type for a batch request.
@@ -91,22 +409,100 @@ where
     Associations: Default,
 {
     /// The status result of the batch request.
+    #[serde(default)]
     pub status: String,
     /// The result objects of the batch request.
+    #[serde(default = "Vec::new")]
     pub results: Vec<HubspotRecord<Properties, PropertiesWithHistory, Associations>>,
-    #[serde(alias = "requestedAt")]
+    /// The number of errors, when some inputs failed.
+    #[serde(alias = "numErrors", default)]
+    pub num_errors: usize,
+    /// The errors of the inputs that failed, returned with a 207 Multi-Status response.
+    #[serde(default)]
+    pub errors: Vec<BatchError>,
+    #[serde(alias = "requestedAt", default)]
     /// The time the batch request was requested.
     pub requested_at: String,
     /// The time the batch request started.
-    #[serde(alias = "startedAt")]
+    #[serde(alias = "startedAt", default)]
     pub started_at: String,
     /// The time the batch request was completed at.
-    #[serde(alias = "completedAt")]
+    #[serde(alias = "completedAt", default)]
     pub completed_at: String,
     /// Links for the batch request.
+    #[serde(default)]
     pub links: HashMap<String, String>,
 }
 
+impl<Properties, PropertiesWithHistory, Associations> BatchResponse
+    for BatchResult<Properties, PropertiesWithHistory, Associations>
+where
+    PropertiesWithHistory: Default,
+    Associations: Default,
+{
+    fn merge(chunks: Vec<Self>) -> Self {
+        let mut status = BatchStatus::default();
+        let mut results = Vec::new();
+        let mut errors = Vec::new();
+        let mut links = HashMap::new();
+
+        for chunk in chunks {
+            status.merge(
+                chunk.status,
+                chunk.requested_at,
+                chunk.started_at,
+                chunk.completed_at,
+            );
+            results.extend(chunk.results);
+            errors.extend(chunk.errors);
+            links.extend(chunk.links);
+        }
+
+        Self {
+            status: status.status,
+            results,
+            num_errors: errors.len(),
+            errors,
+            requested_at: status.requested_at,
+            started_at: status.started_at,
+            completed_at: status.completed_at,
+            links,
+        }
+    }
+
+    fn errors_mut(&mut self) -> &mut Vec<BatchError> {
+        &mut self.errors
+    }
+}
+
+/// The key a value of a unique property is matched by. Hubspot compares emails ignoring case.
+fn unique_key(id_property: &str, value: &str) -> String {
+    if id_property == "email" {
+        value.to_lowercase()
+    } else {
+        value.to_owned()
+    }
+}
+
+/// Deserialize the properties of a record read as json.
+fn with_properties<Properties>(
+    record: HubspotRecord<Value, OptionNotDesired, OptionNotDesired>,
+) -> HubspotResult<HubspotRecord<Properties, OptionNotDesired, OptionNotDesired>>
+where
+    Properties: DeserializeOwned,
+{
+    Ok(HubspotRecord {
+        id: record.id,
+        properties: serde_json::from_value(record.properties)?,
+        associations: record.associations,
+        properties_with_history: record.properties_with_history,
+        created_at: record.created_at,
+        updated_at: record.updated_at,
+        archived: record.archived,
+        archived_at: record.archived_at,
+    })
+}
+
 // Batch Api Collection
 #[derive(Clone, Debug)]
 pub struct BatchApiCollection<T>(T, Arc<HubspotClient>);
@@ -133,107 +529,932 @@ where
         Self(name, client)
     }
 
-    /// Archive a batch of objects by ID
-    pub async fn archive(&self, ids: Vec<&str>) -> HubspotResult<()> {
-        self.client()
-            .send(
+    /// Archive a batch of objects by ID.
+    ///
+    /// Inputs are chunked as in [BatchApiCollection::create]. Hubspot returns no records,
+    /// so the result only holds the errors of the inputs that failed.
+    pub async fn archive(
+        &self,
+        ids: Vec<&str>,
+        options: BatchOptions,
+    ) -> HubspotResult<BatchResult<OptionNotDesired, OptionNotDesired, OptionNotDesired>> {
+        let inputs = ids
+            .into_iter()
+            .map(|id| BatchIdInput { id: id.to_owned() })
+            .collect::<Vec<_>>();
+
+        send_batch(
+            inputs,
+            options,
+            |input| Some(&input.id),
+            |inputs| {
                 self.client()
-                    .begin(
-                        Method::DELETE,
-                        &format!("crm/v3/objects/{}/batch/archive", self.path()),
+                    .send::<Option<BatchResult<OptionNotDesired, OptionNotDesired, OptionNotDesired>>>(
+                        self.client()
+                            .begin(
+                                Method::POST,
+                                &format!("crm/v3/objects/{}/batch/archive", self.path()),
+                            )
+                            .json::<BatchInputs<BatchIdInput>>(&BatchInputs::new(inputs)),
                     )
-                    .json::<BatchInputs<BatchIdInput>>(&BatchInputs::<BatchIdInput> {
-                        inputs: ids
-                            .iter()
-                            .map(|i| BatchIdInput { id: i.to_string() })
-                            .collect(),
-                    }),
-            )
-            .await
+                    .map(|result| result.map(Option::unwrap_or_default))
+            },
+        )
+        .await
     }
 
-    /// Creates a batch of objects
+    /// Creates a batch of objects, each with its own associations.
+    ///
+    /// Inputs beyond the chunk size of the options are sent in multiple requests, and their results merged.
+    /// Hubspot does not return the created records in input order.
+    /// Failed inputs are handled by the error policy of the options.
     pub async fn create<Properties>(
         &self,
-        objects_to_create: Vec<Properties>,
-    ) -> HubspotResult<HubspotRecord<Properties, OptionNotDesired, OptionNotDesired>>
+        objects_to_create: Vec<HubspotRecord<Properties, OptionNotDesired, Vec<CreateAssociation>>>,
+        options: BatchOptions,
+    ) -> HubspotResult<BatchResult<Properties, OptionNotDesired, OptionNotDesired>>
     where
-        Properties: Serialize + DeserializeOwned + Send + Sync + Clone,
+        Properties: Serialize + DeserializeOwned + Send + Sync,
     {
-        self.client()
-            .send::<HubspotRecord<Properties, OptionNotDesired, OptionNotDesired>>(
+        let inputs = objects_to_create
+            .into_iter()
+            .map(|record| BatchCreateInput {
+                properties: record.properties,
+                associations: record.associations,
+            })
+            .collect::<Vec<_>>();
+
+        send_batch(
+            inputs,
+            options,
+            |_| None,
+            |inputs| {
                 self.client()
-                    .begin(Method::POST, &format!("crm/v4/objects/{}", self.path()))
-                    .json::<BatchInputs<BatchPropertiesInput<Properties>>>(&BatchInputs::<
-                        BatchPropertiesInput<Properties>,
-                    > {
-                        inputs: objects_to_create
-                            .into_iter()
-                            .map(|properties| BatchPropertiesInput { properties })
-                            .collect(),
-                    }),
-            )
-            .await
+                    .send::<BatchResult<Properties, OptionNotDesired, OptionNotDesired>>(
+                        self.client()
+                            .begin(
+                                Method::POST,
+                                &format!("crm/v3/objects/{}/batch/create", self.path()),
+                            )
+                            .json::<BatchInputs<BatchCreateInput<Properties>>>(&BatchInputs::new(
+                                inputs,
+                            )),
+                    )
+            },
+        )
+        .await
     }
 
-    /// Read a batch of objects by internal ID
-    pub async fn read<Properties, PropertiesWithHistory, Associations>(
+    /// Read a batch of objects by ID, or by the value of the unique property `id_property`.
+    ///
+    /// Properties:  A struct of the properties to be returned in the response.
+    ///     If the requested object doesn't have a value for a property, it will not appear in the response.
+    ///
+    /// PropertiesWithHistory:  A struct of the properties with history to be returned in the response.
+    ///     If the requested object doesn't have a value for a property, it will not appear in the response.
+    ///
+    /// Inputs are chunked as in [BatchApiCollection::create].
+    pub async fn read<Properties, PropertiesWithHistory>(
         &self,
         ids: Vec<&str>,
-        properties: Properties,
-        properties_with_history: PropertiesWithHistory,
-        associations: Associations,
+        id_property: Option<&str>,
         archived: Option<bool>,
-    ) -> HubspotResult<BatchResult<Properties, PropertiesWithHistory, Associations>>
+        options: BatchOptions,
+    ) -> HubspotResult<BatchResult<Properties, PropertiesWithHistory, OptionNotDesired>>
+    where
+        Properties: DeserializeOwned,
+        PropertiesWithHistory: DeserializeOwned + Default,
+    {
+        let properties = serde_introspect::<Properties>()
+            .iter()
+            .map(|property| property.to_string())
+            .collect::<Vec<String>>();
+        let properties_with_history = serde_introspect::<PropertiesWithHistory>()
+            .iter()
+            .map(|property| property.to_string())
+            .collect::<Vec<String>>();
+        let inputs = ids
+            .into_iter()
+            .map(|id| BatchIdInput { id: id.to_string() })
+            .collect::<Vec<BatchIdInput>>();
+
+        send_batch(
+            inputs,
+            options,
+            |input| Some(&input.id),
+            |inputs| {
+                self.client()
+                    .send::<BatchResult<Properties, PropertiesWithHistory, OptionNotDesired>>(
+                        self.client()
+                            .begin(
+                                Method::POST,
+                                &format!(
+                                    "crm/v3/objects/{}/batch/read{}",
+                                    self.path(),
+                                    Query::new().push("archived", archived.unwrap_or(false))
+                                ),
+                            )
+                            .json::<BatchReadInputs>(&BatchReadInputs {
+                                inputs,
+                                properties: &properties,
+                                properties_with_history: &properties_with_history,
+                                id_property,
+                            }),
+                    )
+            },
+        )
+        .await
+    }
+
+    /// Update a batch of objects, each with its own properties.
+    ///
+    /// Each input is the record's ID and the properties to set. When `id_property` is given,
+    /// records are identified by the value of that unique property instead, eg. contacts by `email`.
+    /// Inputs that change the unique property itself are first resolved to their IDs with a batch read,
+    /// and updated by ID. Records updated by their unique property are matched to their input by the
+    /// property's value in the response, so their outcome has no record if Hubspot doesn't return it.
+    /// Values of `email` are compared ignoring case, as Hubspot does.
+    /// Inputs are chunked as in [BatchApiCollection::create].
+    ///
+    /// Returns an outcome for each input, in input order, or [HubspotError::InvalidInput]
+    /// if the same record is given more than once.
+    pub async fn update<Properties>(
+        &self,
+        inputs: Vec<(String, Properties)>,
+        id_property: Option<&str>,
+        options: BatchOptions,
+    ) -> HubspotResult<Vec<BatchUpdateOutcome<Properties>>>
     where
         Properties: Serialize + DeserializeOwned + Send + Sync,
-        PropertiesWithHistory: Serialize + DeserializeOwned + Default + Send + Sync,
-        Associations: Serialize + DeserializeOwned + Default + Send + Sync,
     {
-        self.client()
-            .send::<BatchResult<Properties, PropertiesWithHistory, Associations>>(
+        let key = |value: &str| match id_property {
+            Some(id_property) => unique_key(id_property, value),
+            None => value.to_owned(),
+        };
+
+        let mut keys = HashSet::with_capacity(inputs.len());
+        let mut to_resolve = Vec::new();
+        let mut changes_id_property = Vec::with_capacity(inputs.len());
+        for (input_id, properties) in &inputs {
+            if !keys.insert(key(input_id)) {
+                return Err(HubspotError::InvalidInput(format!(
+                    "The record {input_id} is updated more than once"
+                )));
+            }
+            let changes = match id_property {
+                Some(id_property) => serde_json::to_value(properties)?
+                    .get(id_property)
+                    .is_some_and(|value| !value.is_null()),
+                None => false,
+            };
+            if changes {
+                to_resolve.push(input_id.clone());
+            }
+            changes_id_property.push(changes);
+        }
+
+        let (record_ids, resolve_errors) = match id_property {
+            Some(id_property) if !to_resolve.is_empty() => {
+                self.resolve_ids(to_resolve, id_property, options).await?
+            }
+            _ => (HashMap::new(), HashMap::new()),
+        };
+
+        let mut targets = Vec::with_capacity(inputs.len());
+        let mut update_inputs = Vec::with_capacity(inputs.len());
+        for ((input_id, properties), changes) in inputs.into_iter().zip(changes_id_property) {
+            let target = match id_property {
+                Some(_) if changes => record_ids
+                    .get(&key(&input_id))
+                    .cloned()
+                    .map(UpdateTarget::Id),
+                Some(_) => Some(UpdateTarget::Property(key(&input_id))),
+                None => Some(UpdateTarget::Id(input_id.clone())),
+            };
+            match &target {
+                Some(UpdateTarget::Id(id)) => update_inputs.push(BatchUpdateInput {
+                    id: id.clone(),
+                    id_property: None,
+                    properties,
+                }),
+                Some(UpdateTarget::Property(_)) => update_inputs.push(BatchUpdateInput {
+                    id: input_id.clone(),
+                    id_property: id_property.map(str::to_owned),
+                    properties,
+                }),
+                None => {}
+            }
+            targets.push((input_id, target));
+        }
+
+        let result = send_batch(
+            update_inputs,
+            options,
+            |input| Some(&input.id),
+            |inputs| {
                 self.client()
-                    .begin(
-                        Method::POST,
-                        &format!("crm/v3/objects/{}/batch/read", self.path()),
+                    .send::<BatchResult<Value, OptionNotDesired, OptionNotDesired>>(
+                        self.client()
+                            .begin(
+                                Method::POST,
+                                &format!("crm/v3/objects/{}/batch/update", self.path()),
+                            )
+                            .json::<BatchInputs<BatchUpdateInput<Properties>>>(&BatchInputs::new(
+                                inputs,
+                            )),
                     )
-                    .json::<BatchReadInputs<Properties, PropertiesWithHistory, Associations>>(
-                        &BatchReadInputs::<Properties, PropertiesWithHistory, Associations> {
-                            properties,
-                            properties_with_history,
-                            associations,
-                            archived: archived.unwrap_or(false),
-                            inputs: ids
-                                .into_iter()
-                                .map(|i| BatchIdInput { id: i.to_string() })
-                                .collect::<Vec<BatchIdInput>>(),
-                        },
+            },
+        )
+        .await?;
+
+        // Errors are keyed by the record ID or unique property value the input was sent with.
+        let mut errors = HashMap::new();
+        for error in result.errors {
+            for id in error.ids() {
+                errors.insert(key(id), error.clone());
+            }
+        }
+        let mut record_ids_by_value = HashMap::new();
+        if let Some(id_property) = id_property {
+            for record in &result.results {
+                if let Some(value) = record.properties.get(id_property).and_then(Value::as_str) {
+                    record_ids_by_value.insert(key(value), record.id.clone());
+                }
+            }
+        }
+        let mut records = result
+            .results
+            .into_iter()
+            .map(|record| (record.id.clone(), record))
+            .collect::<HashMap<_, _>>();
+
+        targets
+            .into_iter()
+            .map(|(input_id, target)| {
+                let (record, error) = match target {
+                    Some(UpdateTarget::Id(id)) => (records.remove(&id), errors.get(&id).cloned()),
+                    Some(UpdateTarget::Property(value)) => (
+                        record_ids_by_value
+                            .get(&value)
+                            .and_then(|id| records.remove(id)),
+                        errors.get(&value).cloned(),
                     ),
-            )
-            .await
+                    None => (None, resolve_errors.get(&key(&input_id)).cloned()),
+                };
+                Ok(BatchUpdateOutcome {
+                    input_id,
+                    record: record.map(with_properties).transpose()?,
+                    error,
+                })
+            })
+            .collect()
     }
 
-    // Update a batch of objects
-    pub async fn update<Properties, PropertiesWithHistory>(
+    /// Resolve the values of a unique property to the IDs of their records, keyed by [unique_key].
+    ///
+    /// Returns the errors of the values that could not be read, eg. those with no record, by the same key.
+    async fn resolve_ids(
         &self,
-        ids: Vec<String>,
-        properties: Properties,
-    ) -> HubspotResult<BatchResult<Properties, PropertiesWithHistory, OptionNotDesired>>
+        values: Vec<String>,
+        id_property: &str,
+        options: BatchOptions,
+    ) -> HubspotResult<(HashMap<String, String>, HashMap<String, BatchError>)> {
+        let properties = [id_property.to_owned()];
+        let inputs = values
+            .into_iter()
+            .map(|id| BatchIdInput { id })
+            .collect::<Vec<_>>();
+
+        let result = send_batch(
+            inputs,
+            options,
+            |input| Some(&input.id),
+            |inputs| {
+                self.client()
+                    .send::<BatchResult<Value, OptionNotDesired, OptionNotDesired>>(
+                        self.client()
+                            .begin(
+                                Method::POST,
+                                &format!("crm/v3/objects/{}/batch/read", self.path()),
+                            )
+                            .json::<BatchReadInputs>(&BatchReadInputs {
+                                inputs,
+                                properties: &properties,
+                                properties_with_history: &[],
+                                id_property: Some(id_property),
+                            }),
+                    )
+            },
+        )
+        .await?;
+
+        let ids = result
+            .results
+            .into_iter()
+            .filter_map(|record| {
+                let value = record.properties.get(id_property)?.as_str()?;
+                Some((unique_key(id_property, value), record.id))
+            })
+            .collect();
+        let mut errors = HashMap::new();
+        for error in result.errors {
+            for id in error.ids() {
+                errors.insert(unique_key(id_property, id), error.clone());
+            }
+        }
+        Ok((ids, errors))
+    }
+
+    /// Create or update a batch of objects by a unique property, eg. contacts by `email`.
+    ///
+    /// Each input is the unique property's value and the properties to set.
+    /// Records are created when no record has the value, and updated otherwise.
+    /// Inputs are chunked as in [BatchApiCollection::create].
+    pub async fn upsert<Properties>(
+        &self,
+        id_property: &str,
+        inputs: Vec<(String, Properties)>,
+        options: BatchOptions,
+    ) -> HubspotResult<BatchResult<Properties, OptionNotDesired, OptionNotDesired>>
     where
-        Properties: Serialize + DeserializeOwned + Send + Sync + Clone,
-        PropertiesWithHistory: DeserializeOwned + Default,
+        Properties: Serialize + DeserializeOwned + Send + Sync,
     {
-        self.client()
-            .send::<BatchResult<Properties, PropertiesWithHistory, OptionNotDesired>>(
+        let inputs = inputs
+            .into_iter()
+            .map(|(id, properties)| BatchUpsertInput {
+                id_property: id_property.to_owned(),
+                id,
+                properties,
+            })
+            .collect::<Vec<_>>();
+
+        send_batch(
+            inputs,
+            options,
+            |input| Some(&input.id),
+            |inputs| {
                 self.client()
-                    .begin(
-                        Method::PATCH,
-                        &format!("/crm/v3/objects/{}/batch/update", self.path()),
+                    .send::<BatchResult<Properties, OptionNotDesired, OptionNotDesired>>(
+                        self.client()
+                            .begin(
+                                Method::POST,
+                                &format!("crm/v3/objects/{}/batch/upsert", self.path()),
+                            )
+                            .json::<BatchInputs<BatchUpsertInput<Properties>>>(&BatchInputs::new(
+                                inputs,
+                            )),
                     )
-                    .json::<BatchInputs<BatchUpdateInput<Properties>>>(&BatchInputs::new(
-                        BatchUpdateInput::new_batch(ids, properties),
-                    )),
+            },
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::StatusCode;
+    use serde_json::json;
+
+    use super::*;
+    use crate::client::transport::{HttpResponse, MockTransport};
+    use crate::Hubspot;
+
+    #[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+    struct Contact {
+        email: String,
+    }
+
+    fn hubspot(transport: &Arc<MockTransport>) -> Hubspot {
+        Hubspot::builder()
+            .domain("api.hubapi.com")
+            .token("token")
+            .portal_id("1")
+            .transport(Arc::clone(transport) as Arc<_>)
+            .build()
+            .unwrap()
+    }
+
+    fn contact(email: &str) -> HubspotRecord<Contact, OptionNotDesired, Vec<CreateAssociation>> {
+        HubspotRecord {
+            properties: Contact {
+                email: email.to_owned(),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn record(id: &str, email: &str) -> Value {
+        json!({ "id": id, "properties": { "email": email } })
+    }
+
+    fn request_bodies(transport: &MockTransport) -> Vec<Value> {
+        transport
+            .requests()
+            .iter()
+            .map(|request| serde_json::from_slice(request.body.as_deref().unwrap()).unwrap())
+            .collect()
+    }
+
+    fn input_ids(body: &Value) -> Vec<&str> {
+        body["inputs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|input| input["id"].as_str().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn splits_inputs_into_chunks_and_merges_the_results() {
+        let transport = Arc::new(MockTransport::new());
+        for chunk in [0..100, 100..200, 200..250] {
+            let results = chunk
+                .map(|i| record(&i.to_string(), "a@example.com"))
+                .collect::<Vec<_>>();
+            transport.respond_json(
+                StatusCode::OK,
+                &json!({ "status": "COMPLETE", "results": results }),
+            );
+        }
+        let ids = (0..250).map(|i| i.to_string()).collect::<Vec<_>>();
+
+        let result = hubspot(&transport)
+            .objects
+            .contacts
+            .batch
+            .read::<Contact, OptionNotDesired>(
+                ids.iter().map(String::as_str).collect(),
+                None,
+                None,
+                BatchOptions::new(),
+            )
+            .await
+            .unwrap();
+
+        let chunk_sizes = request_bodies(&transport)
+            .iter()
+            .map(|body| input_ids(body).len())
+            .collect::<Vec<_>>();
+        assert_eq!(chunk_sizes, vec![100, 100, 50]);
+        assert_eq!(result.results.len(), 250);
+        assert_eq!(result.status, "COMPLETE");
+        assert_eq!(result.num_errors, 0);
+    }
+
+    #[tokio::test]
+    async fn reads_by_unique_property_with_the_requested_properties() {
+        #[derive(Deserialize, Debug, Default)]
+        struct ContactHistory {
+            #[serde(rename = "lifecyclestage")]
+            _lifecycle_stage: Vec<Value>,
+        }
+
+        let transport = Arc::new(MockTransport::new());
+        transport.respond_json(
+            StatusCode::OK,
+            &json!({ "status": "COMPLETE", "results": [record("1", "a@example.com")] }),
+        );
+
+        let result = hubspot(&transport)
+            .objects
+            .contacts
+            .batch
+            .read::<Contact, ContactHistory>(
+                vec!["a@example.com"],
+                Some("email"),
+                Some(true),
+                BatchOptions::new(),
+            )
+            .await
+            .unwrap();
+
+        let request = &transport.requests()[0];
+        assert_eq!(request.url.path(), "/crm/v3/objects/contacts/batch/read");
+        assert_eq!(request.url.query(), Some("archived=true"));
+        assert_eq!(
+            request_bodies(&transport)[0],
+            json!({
+                "inputs": [{ "id": "a@example.com" }],
+                "properties": ["email"],
+                "propertiesWithHistory": ["lifecyclestage"],
+                "idProperty": "email",
+            })
+        );
+        assert_eq!(result.results[0].properties.email, "a@example.com");
+    }
+
+    #[tokio::test]
+    async fn keeps_the_results_of_other_chunks_when_a_chunk_fails() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond_json(
+            StatusCode::OK,
+            &json!({ "status": "COMPLETE", "results": [record("1", "a@example.com")] }),
+        );
+        transport.respond_json(
+            StatusCode::BAD_REQUEST,
+            &json!({ "message": "Invalid input", "category": "VALIDATION_ERROR" }),
+        );
+        transport.respond_json(
+            StatusCode::OK,
+            &json!({ "status": "COMPLETE", "results": [record("3", "c@example.com")] }),
+        );
+
+        let result = hubspot(&transport)
+            .objects
+            .contacts
+            .batch
+            .read::<Contact, OptionNotDesired>(
+                vec!["1", "2", "3"],
+                None,
+                None,
+                BatchOptions::new().chunk_size(1),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(transport.requests().len(), 3);
+        let ids = result
+            .results
+            .iter()
+            .map(|record| record.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["1", "3"]);
+        assert_eq!(result.num_errors, 1);
+        assert_eq!(result.errors[0].ids(), ["2"]);
+        assert_eq!(result.errors[0].category, "VALIDATION_ERROR");
+        assert_eq!(
+            result.errors[0].request_status,
+            Some(StatusCode::BAD_REQUEST)
+        );
+    }
+
+    #[tokio::test]
+    async fn fails_when_no_request_succeeds() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond_json(
+            StatusCode::UNAUTHORIZED,
+            &json!({ "message": "Authentication credentials not found", "category": "INVALID_AUTHENTICATION" }),
+        );
+
+        let error = hubspot(&transport)
+            .objects
+            .contacts
+            .batch
+            .read::<Contact, OptionNotDesired>(vec!["1", "2"], None, None, BatchOptions::new())
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.status(), Some(StatusCode::UNAUTHORIZED));
+    }
+
+    #[tokio::test]
+    async fn reports_the_positions_of_the_inputs_of_a_failed_request() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond_json(
+            StatusCode::CREATED,
+            &json!({ "status": "COMPLETE", "results": [record("1", "a@example.com"), record("2", "b@example.com")] }),
+        );
+        transport.respond_json(
+            StatusCode::BAD_REQUEST,
+            &json!({ "message": "Invalid input", "category": "VALIDATION_ERROR" }),
+        );
+
+        let result = hubspot(&transport)
+            .objects
+            .contacts
+            .batch
+            .create(
+                ["a", "b", "c"]
+                    .iter()
+                    .map(|name| contact(&format!("{name}@example.com")))
+                    .collect(),
+                BatchOptions::new().chunk_size(2),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.results.len(), 2);
+        assert_eq!(result.errors[0].input_indexes, vec![2]);
+        assert!(result.errors[0].ids().is_empty());
+    }
+
+    #[test]
+    fn merges_the_timestamps_of_the_chunks() {
+        let chunk = |requested_at: &str, completed_at: &str| BatchResult::<
+            Value,
+            OptionNotDesired,
+            OptionNotDesired,
+        > {
+            status: "COMPLETE".to_owned(),
+            requested_at: requested_at.to_owned(),
+            started_at: requested_at.to_owned(),
+            completed_at: completed_at.to_owned(),
+            ..Default::default()
+        };
+        let mut failed = BatchResult::merge(Vec::new());
+        failed.errors.push(BatchError::from_failed_request(
+            &HubspotError::UnexpectedResponse("failed".to_owned()),
+            Vec::new(),
+            vec![0],
+        ));
+
+        let merged = BatchResult::merge(vec![
+            failed,
+            chunk("2024-01-01T00:00:02Z", "2024-01-01T00:00:03Z"),
+            chunk("2024-01-01T00:00:01Z", "2024-01-01T00:00:04Z"),
+        ]);
+
+        assert_eq!(merged.status, "COMPLETE");
+        assert_eq!(merged.requested_at, "2024-01-01T00:00:01Z");
+        assert_eq!(merged.started_at, "2024-01-01T00:00:01Z");
+        assert_eq!(merged.completed_at, "2024-01-01T00:00:04Z");
+        assert_eq!(merged.num_errors, 1);
+    }
+
+    #[tokio::test]
+    async fn fail_on_error_returns_the_failed_inputs() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond_json(
+            StatusCode::MULTI_STATUS,
+            &json!({
+                "status": "COMPLETE",
+                "results": [record("1", "a@example.com")],
+                "numErrors": 1,
+                "errors": [{ "status": "error", "category": "OBJECT_NOT_FOUND", "message": "Not found", "context": { "ids": ["2"] } }]
+            }),
+        );
+
+        let error = hubspot(&transport)
+            .objects
+            .contacts
+            .batch
+            .read::<Contact, OptionNotDesired>(
+                vec!["1", "2"],
+                None,
+                None,
+                BatchOptions::new().error_policy(BatchErrorPolicy::FailOnError),
             )
             .await
+            .unwrap_err();
+
+        assert_eq!(error.batch_errors().len(), 1);
+        assert_eq!(error.batch_errors()[0].ids(), ["2"]);
+    }
+
+    #[tokio::test]
+    async fn retries_only_transient_failures() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond_json(
+            StatusCode::MULTI_STATUS,
+            &json!({
+                "status": "COMPLETE",
+                "results": [record("1", "a@example.com")],
+                "numErrors": 2,
+                "errors": [
+                    { "status": "error", "category": "RATE_LIMITS", "message": "Slow down", "context": { "ids": ["2"] } },
+                    { "status": "error", "category": "OBJECT_NOT_FOUND", "message": "Not found", "context": { "ids": ["3"] } }
+                ]
+            }),
+        );
+        transport.respond_json(
+            StatusCode::OK,
+            &json!({ "status": "COMPLETE", "results": [record("2", "b@example.com")] }),
+        );
+
+        let result = hubspot(&transport)
+            .objects
+            .contacts
+            .batch
+            .read::<Contact, OptionNotDesired>(
+                vec!["1", "2", "3"],
+                None,
+                None,
+                BatchOptions::new()
+                    .error_policy(BatchErrorPolicy::RetryFailed { max_retries: 3 })
+                    .retry_delay(Duration::ZERO),
+            )
+            .await
+            .unwrap();
+
+        let bodies = request_bodies(&transport);
+        assert_eq!(bodies.len(), 2);
+        assert_eq!(input_ids(&bodies[1]), vec!["2"]);
+        assert_eq!(result.results.len(), 2);
+        assert_eq!(result.num_errors, 1);
+        assert_eq!(result.errors[0].ids(), ["3"]);
+    }
+
+    #[tokio::test]
+    async fn resends_a_rate_limited_create_request() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond_json(
+            StatusCode::TOO_MANY_REQUESTS,
+            &json!({ "message": "Slow down", "category": "RATE_LIMITS" }),
+        );
+        transport.respond_json(
+            StatusCode::CREATED,
+            &json!({ "status": "COMPLETE", "results": [record("2", "b@example.com")] }),
+        );
+        transport.respond_json(
+            StatusCode::CREATED,
+            &json!({ "status": "COMPLETE", "results": [record("1", "a@example.com")] }),
+        );
+
+        let result = hubspot(&transport)
+            .objects
+            .contacts
+            .batch
+            .create(
+                vec![contact("a@example.com"), contact("b@example.com")],
+                BatchOptions::new()
+                    .chunk_size(1)
+                    .error_policy(BatchErrorPolicy::RetryFailed { max_retries: 1 })
+                    .retry_delay(Duration::ZERO),
+            )
+            .await
+            .unwrap();
+
+        let bodies = request_bodies(&transport);
+        assert_eq!(bodies.len(), 3);
+        assert_eq!(bodies[2], bodies[0]);
+        assert_eq!(
+            bodies[2]["inputs"][0]["properties"]["email"],
+            "a@example.com"
+        );
+        assert_eq!(result.results.len(), 2);
+        assert_eq!(result.num_errors, 0);
+    }
+
+    #[tokio::test]
+    async fn archives_in_chunks() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond(HttpResponse::new(StatusCode::NO_CONTENT, Vec::new()));
+        transport.respond_json(
+            StatusCode::MULTI_STATUS,
+            &json!({
+                "status": "COMPLETE",
+                "numErrors": 1,
+                "errors": [{ "status": "error", "category": "OBJECT_NOT_FOUND", "message": "Not found", "context": { "ids": ["3"] } }]
+            }),
+        );
+
+        let result = hubspot(&transport)
+            .objects
+            .contacts
+            .batch
+            .archive(vec!["1", "2", "3"], BatchOptions::new().chunk_size(2))
+            .await
+            .unwrap();
+
+        let requests = transport.requests();
+        assert!(requests.iter().all(|request| request.method == Method::POST
+            && request.url.path() == "/crm/v3/objects/contacts/batch/archive"));
+        let chunks = request_bodies(&transport)
+            .iter()
+            .map(|body| input_ids(body).len())
+            .collect::<Vec<_>>();
+        assert_eq!(chunks, vec![2, 1]);
+        assert_eq!(result.num_errors, 1);
+        assert_eq!(result.errors[0].ids(), ["3"]);
+    }
+
+    #[tokio::test]
+    async fn updates_by_the_native_unique_property() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond_json(
+            StatusCode::MULTI_STATUS,
+            &json!({
+                "status": "COMPLETE",
+                "results": [{ "id": "11", "properties": { "email": "jane@example.com", "firstname": "Jane" } }],
+                "numErrors": 1,
+                "errors": [{ "status": "error", "category": "OBJECT_NOT_FOUND", "message": "Not found", "context": { "ids": ["gone@example.com"] } }]
+            }),
+        );
+
+        let outcomes = hubspot(&transport)
+            .objects
+            .contacts
+            .batch
+            .update(
+                vec![
+                    (
+                        "Jane@Example.com".to_owned(),
+                        json!({ "firstname": "Jane" }),
+                    ),
+                    (
+                        "gone@example.com".to_owned(),
+                        json!({ "firstname": "Gone" }),
+                    ),
+                ],
+                Some("email"),
+                BatchOptions::new(),
+            )
+            .await
+            .unwrap();
+
+        let bodies = request_bodies(&transport);
+        assert_eq!(bodies.len(), 1);
+        assert_eq!(
+            bodies[0]["inputs"][0],
+            json!({ "id": "Jane@Example.com", "idProperty": "email", "properties": { "firstname": "Jane" } })
+        );
+
+        assert_eq!(outcomes[0].input_id, "Jane@Example.com");
+        assert_eq!(outcomes[0].record.as_ref().unwrap().id, "11");
+        assert!(outcomes[0].error.is_none());
+        assert!(outcomes[1].record.is_none());
+        assert_eq!(
+            outcomes[1].error.as_ref().unwrap().category,
+            "OBJECT_NOT_FOUND"
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_updating_a_record_twice() {
+        let transport = Arc::new(MockTransport::new());
+
+        let error = hubspot(&transport)
+            .objects
+            .contacts
+            .batch
+            .update(
+                vec![
+                    (
+                        "jane@example.com".to_owned(),
+                        json!({ "firstname": "Jane" }),
+                    ),
+                    (
+                        "JANE@example.com".to_owned(),
+                        json!({ "firstname": "Janet" }),
+                    ),
+                ],
+                Some("email"),
+                BatchOptions::new(),
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, HubspotError::InvalidInput(_)));
+        assert!(transport.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn resolves_record_ids_when_the_unique_property_changes() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond_json(
+            StatusCode::MULTI_STATUS,
+            &json!({
+                "status": "COMPLETE",
+                "results": [record("11", "Jane@Example.com")],
+                "numErrors": 1,
+                "errors": [{ "status": "error", "category": "OBJECT_NOT_FOUND", "message": "Not found", "context": { "ids": ["gone@example.com"] } }]
+            }),
+        );
+        transport.respond_json(
+            StatusCode::OK,
+            &json!({ "status": "COMPLETE", "results": [record("11", "jane@new.example.com")] }),
+        );
+
+        let outcomes = hubspot(&transport)
+            .objects
+            .contacts
+            .batch
+            .update(
+                vec![
+                    (
+                        "jane@example.com".to_owned(),
+                        Contact {
+                            email: "jane@new.example.com".to_owned(),
+                        },
+                    ),
+                    (
+                        "gone@example.com".to_owned(),
+                        Contact {
+                            email: "other@example.com".to_owned(),
+                        },
+                    ),
+                ],
+                Some("email"),
+                BatchOptions::new(),
+            )
+            .await
+            .unwrap();
+
+        let bodies = request_bodies(&transport);
+        assert_eq!(bodies[0]["idProperty"], "email");
+        assert_eq!(
+            bodies[1],
+            json!({ "inputs": [{ "id": "11", "properties": { "email": "jane@new.example.com" } }] })
+        );
+
+        assert_eq!(outcomes[0].input_id, "jane@example.com");
+        assert_eq!(outcomes[0].record.as_ref().unwrap().id, "11");
+        assert!(outcomes[0].error.is_none());
+        assert_eq!(outcomes[1].input_id, "gone@example.com");
+        assert!(outcomes[1].record.is_none());
+        assert_eq!(
+            outcomes[1].error.as_ref().unwrap().category,
+            "OBJECT_NOT_FOUND"
+        );
     }
 }
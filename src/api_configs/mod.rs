@@ -1,26 +1,35 @@
 mod associations;
-mod batch;
+pub mod batch;
+pub mod paging;
 pub mod query;
+pub mod search;
 pub mod types;
 
 use std::sync::Arc;
 
-pub use associations::{AssociationCreationDetails, AssociationTypes};
+pub use associations::{
+    Association, AssociationBatchResult, AssociationCreationDetails, AssociationObjectId,
+    AssociationPair, AssociationTypes, CreatedAssociationResult, DefaultAssociation,
+    PrimaryCompanyChange, RecordAssociations,
+};
 
 pub use types::{CreateAssociation, HubspotRecord, OptionNotDesired};
 
 use crate::client::HubspotClient;
 
 use self::associations::AssociationsApiCollection;
-use self::batch::BatchApiCollection;
-use self::query::{build_paging_query, build_query_string};
+use self::batch::{BatchApiCollection, BatchErrorPolicy, BatchOptions};
+use self::paging::{paginate, paginate_pages, PagingOptions};
+use self::query::{encode_path_segment, Query};
+use self::search::{SearchRequest, SearchResult};
 use self::types::{ListResult, ObjectApi, ToPath};
 
+use futures::Stream;
 use reqwest::Method;
 use serde::{de::DeserializeOwned, Serialize};
 use serde_aux::serde_introspection::serde_introspect;
 
-use crate::client::error::HubspotResult;
+use crate::client::error::{HubspotError, HubspotResult};
 
 /// A collection of Hubspot api methods.
 #[derive(Clone, Debug)]
@@ -83,29 +92,114 @@ where
         PropertiesWithHistory: DeserializeOwned + Default,
         Associations: DeserializeOwned + Default,
     {
-        let paging_query = build_paging_query(limit, after);
+        let query = Query::new()
+            .push_opt("limit", limit)
+            .push_opt("after", after)
+            .push_all("properties", serde_introspect::<Properties>())
+            .push_all(
+                "propertiesWithHistory",
+                serde_introspect::<PropertiesWithHistory>(),
+            )
+            .push_all("associations", serde_introspect::<Associations>())
+            .push("archived", archived.unwrap_or(false));
 
         self.client()
             .send::<ListResult<HubspotRecord<Properties, PropertiesWithHistory, Associations>>>(
                 self.client().begin(
                     Method::GET,
-                    &format!(
-                        "crm/v3/objects/{}{}{}",
-                        self.path(),
-                        paging_query.0,
-                        build_query_string(
-                            paging_query.1,
-                            serde_introspect::<Properties>(),
-                            serde_introspect::<PropertiesWithHistory>(),
-                            serde_introspect::<Associations>(),
-                            archived.unwrap_or(false)
-                        )
-                    ),
+                    &format!("crm/v3/objects/{}{}", self.path(), query),
                 ),
             )
             .await
     }
 
+    /// Stream every record of an object type, fetching pages lazily as the stream is consumed.
+    ///
+    /// Properties, PropertiesWithHistory and Associations are requested as in [ApiCollection::list].
+    pub fn list_all<Properties, PropertiesWithHistory, Associations>(
+        &self,
+        options: PagingOptions,
+        archived: Option<bool>,
+    ) -> impl Stream<Item = HubspotResult<HubspotRecord<Properties, PropertiesWithHistory, Associations>>>
+    where
+        Properties: DeserializeOwned,
+        PropertiesWithHistory: DeserializeOwned + Default,
+        Associations: DeserializeOwned + Default,
+    {
+        let collection = self.clone();
+        paginate(options, move |limit, after| {
+            let collection = collection.clone();
+            async move {
+                collection
+                    .list::<Properties, PropertiesWithHistory, Associations>(
+                        limit,
+                        after.as_deref(),
+                        archived,
+                    )
+                    .await
+            }
+        })
+    }
+
+    /// Stream every page of an object type.
+    /// Each page's `paging.next.after` cursor can be saved to resume with [PagingOptions::after].
+    pub fn list_pages<Properties, PropertiesWithHistory, Associations>(
+        &self,
+        options: PagingOptions,
+        archived: Option<bool>,
+    ) -> impl Stream<
+        Item = HubspotResult<
+            ListResult<HubspotRecord<Properties, PropertiesWithHistory, Associations>>,
+        >,
+    >
+    where
+        Properties: DeserializeOwned,
+        PropertiesWithHistory: DeserializeOwned + Default,
+        Associations: DeserializeOwned + Default,
+    {
+        let collection = self.clone();
+        paginate_pages(options, move |limit, after| {
+            let collection = collection.clone();
+            async move {
+                collection
+                    .list::<Properties, PropertiesWithHistory, Associations>(
+                        limit,
+                        after.as_deref(),
+                        archived,
+                    )
+                    .await
+            }
+        })
+    }
+
+    /// Search for records matching the filters, sorts and query of the search request.
+    ///
+    /// Properties:  A struct of the properties to be returned in the response.
+    ///     If the requested object doesn't have a value for a property, it will not appear in the response.
+    pub async fn search<Properties>(
+        &self,
+        mut request: SearchRequest,
+    ) -> HubspotResult<SearchResult<HubspotRecord<Properties, OptionNotDesired, OptionNotDesired>>>
+    where
+        Properties: DeserializeOwned,
+    {
+        request.properties = serde_introspect::<Properties>()
+            .iter()
+            .map(|property| property.to_string())
+            .collect();
+
+        self.client()
+            .send::<SearchResult<HubspotRecord<Properties, OptionNotDesired, OptionNotDesired>>>(
+                self.client()
+                    .begin(
+                        Method::POST,
+                        &format!("crm/v3/objects/{}/search", self.path()),
+                    )
+                    .json::<SearchRequest>(&request),
+            )
+            .await
+    }
+
     /// Creates a new object
     ///
     /// Properties:  A struct of the properties to be returned in the response.
@@ -156,6 +250,47 @@ where
         PropertiesWithHistory: DeserializeOwned + Default,
         Associations: DeserializeOwned + Default,
     {
+        self.read_record(id, None, archived).await
+    }
+
+    /// Returns the object whose unique property `id_property` has the value, eg. a contact by `email`.
+    ///
+    /// Properties, PropertiesWithHistory and Associations are requested as in [ApiCollection::read].
+    pub async fn read_by_property<Properties, PropertiesWithHistory, Associations>(
+        &self,
+        id_property: &str,
+        value: &str,
+        archived: bool,
+    ) -> HubspotResult<HubspotRecord<Properties, PropertiesWithHistory, Associations>>
+    where
+        Properties: DeserializeOwned,
+        PropertiesWithHistory: DeserializeOwned + Default,
+        Associations: DeserializeOwned + Default,
+    {
+        self.read_record(value, Some(id_property), archived).await
+    }
+
+    async fn read_record<Properties, PropertiesWithHistory, Associations>(
+        &self,
+        id: &str,
+        id_property: Option<&str>,
+        archived: bool,
+    ) -> HubspotResult<HubspotRecord<Properties, PropertiesWithHistory, Associations>>
+    where
+        Properties: DeserializeOwned,
+        PropertiesWithHistory: DeserializeOwned + Default,
+        Associations: DeserializeOwned + Default,
+    {
+        let query = Query::new()
+            .push_all("properties", serde_introspect::<Properties>())
+            .push_all(
+                "propertiesWithHistory",
+                serde_introspect::<PropertiesWithHistory>(),
+            )
+            .push_all("associations", serde_introspect::<Associations>())
+            .push("archived", archived)
+            .push_opt("idProperty", id_property);
+
         self.client()
             .send::<HubspotRecord<Properties, PropertiesWithHistory, Associations>>(
                 self.client().begin(
@@ -163,14 +298,8 @@ where
                     &format!(
                         "crm/v3/objects/{}/{}{}",
                         self.path(),
-                        id,
-                        build_query_string(
-                            false,
-                            serde_introspect::<Properties>(),
-                            serde_introspect::<PropertiesWithHistory>(),
-                            serde_introspect::<Associations>(),
-                            archived
-                        )
+                        encode_path_segment(id),
+                        query
                     ),
                 ),
             )
@@ -189,6 +318,36 @@ where
         id: String,
         properties: Properties,
     ) -> HubspotResult<HubspotRecord<Properties, PropertiesWithHistory, OptionNotDesired>>
+    where
+        Properties: Serialize + DeserializeOwned + Send + Sync + std::fmt::Debug,
+        PropertiesWithHistory: DeserializeOwned + Default,
+    {
+        self.update_record(&id, None, properties).await
+    }
+
+    /// Updates the object whose unique property `id_property` has the value, eg. a contact by `email`.
+    ///
+    /// Properties and PropertiesWithHistory are used as in [ApiCollection::update].
+    pub async fn update_by_property<Properties, PropertiesWithHistory>(
+        &self,
+        id_property: &str,
+        value: &str,
+        properties: Properties,
+    ) -> HubspotResult<HubspotRecord<Properties, PropertiesWithHistory, OptionNotDesired>>
+    where
+        Properties: Serialize + DeserializeOwned + Send + Sync + std::fmt::Debug,
+        PropertiesWithHistory: DeserializeOwned + Default,
+    {
+        self.update_record(value, Some(id_property), properties)
+            .await
+    }
+
+    async fn update_record<Properties, PropertiesWithHistory>(
+        &self,
+        id: &str,
+        id_property: Option<&str>,
+        properties: Properties,
+    ) -> HubspotResult<HubspotRecord<Properties, PropertiesWithHistory, OptionNotDesired>>
     where
         Properties: Serialize + DeserializeOwned + Send + Sync + std::fmt::Debug,
         PropertiesWithHistory: DeserializeOwned + Default,
@@ -198,7 +357,12 @@ where
                 self.client()
                     .begin(
                         Method::PATCH,
-                        &format!("crm/v3/objects/{}/{}", self.path(), id,),
+                        &format!(
+                            "crm/v3/objects/{}/{}{}",
+                            self.path(),
+                            encode_path_segment(id),
+                            Query::new().push_opt("idProperty", id_property)
+                        ),
                     )
                     .json::<HubspotRecord<Properties, OptionNotDesired, OptionNotDesired>>(
                         &HubspotRecord::with_properties(properties),
@@ -207,6 +371,32 @@ where
             .await
     }
 
+    /// Creates the object whose unique property `id_property` has the value, or updates it if it exists.
+    ///
+    /// Properties:  A struct of the properties to be set and returned in the response.
+    pub async fn upsert<Properties>(
+        &self,
+        id_property: &str,
+        value: &str,
+        properties: Properties,
+    ) -> HubspotResult<HubspotRecord<Properties, OptionNotDesired, OptionNotDesired>>
+    where
+        Properties: Serialize + DeserializeOwned + Send + Sync,
+    {
+        self.batch
+            .upsert(
+                id_property,
+                vec![(value.to_owned(), properties)],
+                BatchOptions::new().error_policy(BatchErrorPolicy::FailOnError),
+            )
+            .await?
+            .results
+            .pop()
+            .ok_or_else(|| {
+                HubspotError::UnexpectedResponse("The upsert returned no record".to_owned())
+            })
+    }
+
     /// Move an Object identified by id to the recycling bin.
     pub async fn archive(&self, id: String) -> HubspotResult<()> {
         self.client()
@@ -0,0 +1,202 @@
+use std::future::Future;
+
+use futures::{stream, Stream, StreamExt};
+
+use crate::client::error::HubspotResult;
+
+use super::types::ListResult;
+
+/// Options for streaming every record of a paged endpoint.
+#[derive(Clone, Debug, Default)]
+pub struct PagingOptions {
+    page_size: Option<i32>,
+    max_records: Option<usize>,
+    after: Option<String>,
+}
+
+impl PagingOptions {
+    /// Create paging options using Hubspot's default page size.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The number of records to request per page.
+    pub fn page_size(mut self, page_size: i32) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    /// Stop after this many records, even if more pages are available.
+    pub fn max_records(mut self, max_records: usize) -> Self {
+        self.max_records = Some(max_records);
+        self
+    }
+
+    /// Resume from a saved paging cursor, as returned in `paging.next.after`.
+    pub fn after(mut self, after: &str) -> Self {
+        self.after = Some(after.to_owned());
+        self
+    }
+}
+
+/// Lazily fetch each page of a paged endpoint, following the `paging.next.after` cursor.
+///
+/// `fetch` is called with the page size and the cursor of the page to fetch.
+/// The stream ends after the last page, or after the first error.
+pub(crate) fn paginate_pages<T, F, Fut>(
+    options: PagingOptions,
+    fetch: F,
+) -> impl Stream<Item = HubspotResult<ListResult<T>>>
+where
+    F: FnMut(Option<i32>, Option<String>) -> Fut,
+    Fut: Future<Output = HubspotResult<ListResult<T>>>,
+{
+    stream::unfold(
+        (fetch, Some(options.after)),
+        move |(mut fetch, cursor)| async move {
+            let after = cursor?;
+            match fetch(options.page_size, after).await {
+                Ok(page) => {
+                    let next = page
+                        .paging
+                        .as_ref()
+                        .map(|paging| Some(paging.next.after.clone()));
+                    Some((Ok(page), (fetch, next)))
+                }
+                Err(err) => Some((Err(err), (fetch, None))),
+            }
+        },
+    )
+}
+
+/// Lazily fetch every record of a paged endpoint, one page at a time.
+pub(crate) fn paginate<T, F, Fut>(
+    options: PagingOptions,
+    fetch: F,
+) -> impl Stream<Item = HubspotResult<T>>
+where
+    F: FnMut(Option<i32>, Option<String>) -> Fut,
+    Fut: Future<Output = HubspotResult<ListResult<T>>>,
+{
+    let max_records = options.max_records;
+    let records = paginate_pages(options, fetch).flat_map(|page| {
+        stream::iter(match page {
+            Ok(page) => page.results.into_iter().map(Ok).collect::<Vec<_>>(),
+            Err(err) => vec![Err(err)],
+        })
+    });
+
+    match max_records {
+        Some(max_records) => records.take(max_records).left_stream(),
+        None => records.right_stream(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::api_configs::types::{Paging, PagingNext};
+    use crate::client::error::HubspotError;
+
+    /// The page size and cursor of each fetch.
+    type Calls = Arc<Mutex<Vec<(Option<i32>, Option<String>)>>>;
+
+    /// Three pages of two records, following the cursors "2" and "4".
+    fn page(after: Option<&str>) -> HubspotResult<ListResult<u32>> {
+        let start = after.map_or(0, |after| after.parse().unwrap());
+        let next = start + 2;
+
+        Ok(ListResult {
+            results: vec![start, start + 1],
+            paging: (next < 6).then(|| Paging {
+                next: PagingNext {
+                    after: next.to_string(),
+                    link: String::new(),
+                },
+            }),
+        })
+    }
+
+    /// Fetch pages, recording the page size and cursor of each call.
+    fn fetch(
+        calls: &Calls,
+    ) -> impl FnMut(Option<i32>, Option<String>) -> futures::future::Ready<HubspotResult<ListResult<u32>>>
+    {
+        let calls = Arc::clone(calls);
+        move |limit, after| {
+            calls.lock().unwrap().push((limit, after.clone()));
+            futures::future::ready(page(after.as_deref()))
+        }
+    }
+
+    #[tokio::test]
+    async fn follows_the_paging_cursor() {
+        let calls = Calls::default();
+
+        let records: Vec<u32> = paginate(PagingOptions::new().page_size(2), fetch(&calls))
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(records, vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![
+                (Some(2), None),
+                (Some(2), Some("2".to_owned())),
+                (Some(2), Some("4".to_owned())),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn stops_fetching_at_max_records() {
+        let calls = Calls::default();
+
+        let records: Vec<u32> = paginate(PagingOptions::new().max_records(3), fetch(&calls))
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(records, vec![0, 1, 2]);
+        assert_eq!(calls.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn resumes_from_a_saved_cursor() {
+        let calls = Calls::default();
+
+        let pages: Vec<ListResult<u32>> =
+            paginate_pages(PagingOptions::new().after("4"), fetch(&calls))
+                .try_collect()
+                .await
+                .unwrap();
+
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].results, vec![4, 5]);
+        assert!(pages[0].paging.is_none());
+    }
+
+    #[tokio::test]
+    async fn ends_after_the_first_error() {
+        let mut pages = 0;
+        let results: Vec<HubspotResult<u32>> = paginate(PagingOptions::new(), |_, after| {
+            pages += 1;
+            futures::future::ready(if after.is_some() {
+                Err(HubspotError::UnexpectedResponse("failed".to_owned()))
+            } else {
+                page(None)
+            })
+        })
+        .collect()
+        .await;
+
+        assert_eq!(results.len(), 3);
+        assert!(results[2].is_err());
+        assert_eq!(pages, 2);
+    }
+}
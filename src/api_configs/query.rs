@@ -1,76 +1,122 @@
-/// Check if query has begun.
-pub fn query_begun_check(checkpoint: bool) -> (String, bool) {
-    if checkpoint {
-        ("&".to_string(), checkpoint)
-    } else {
-        ("?".to_string(), true)
-    }
+use std::fmt::{Display, Formatter};
+
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+/// Characters left unencoded in query keys and values and in path segments,
+/// being the unreserved characters of RFC 3986.
+const QUERY_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// A query string for a Hubspot request.
+///
+/// Keys and values are percent-encoded, so paging cursors and property values containing
+/// characters such as `+`, `@` or spaces are sent as given.
+/// Displays as an empty string when no parameters have been added, otherwise as `?key=value&...`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Query {
+    params: Vec<(String, String)>,
 }
 
-/// Build the query for hubspot paging.
-pub fn build_paging_query(limit: Option<i32>, after: Option<&str>) -> (String, bool) {
-    let mut query_begun = false;
+impl Query {
+    /// Create an empty query.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Add a parameter.
+    pub fn push<V>(mut self, key: &str, value: V) -> Self
+    where
+        V: ToString,
+    {
+        self.params.push((key.to_owned(), value.to_string()));
+        self
+    }
 
-    let limit_query = match limit {
-        Some(limit) => {
-            query_begun = true;
-            format!("?limit={}", limit)
+    /// Add a parameter if it has a value.
+    pub fn push_opt<V>(self, key: &str, value: Option<V>) -> Self
+    where
+        V: ToString,
+    {
+        match value {
+            Some(value) => self.push(key, value),
+            None => self,
         }
-        None => String::new(),
-    };
-
-    let after_query = match after {
-        Some(after) => {
-            let query_check = query_begun_check(query_begun);
-            query_begun = query_check.1;
-            format!("{}after={}", query_check.0, after)
+    }
+
+    /// Add a parameter once for each of the values.
+    pub fn push_all<I>(self, key: &str, values: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: ToString,
+    {
+        values
+            .into_iter()
+            .fold(self, |query, value| query.push(key, value))
+    }
+}
+
+impl Display for Query {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, (key, value)) in self.params.iter().enumerate() {
+            write!(
+                f,
+                "{}{}={}",
+                if i == 0 { "?" } else { "&" },
+                utf8_percent_encode(key, QUERY_ENCODE_SET),
+                utf8_percent_encode(value, QUERY_ENCODE_SET)
+            )?;
         }
-        None => String::new(),
-    };
 
-    (format!("{}{}", limit_query, after_query), query_begun)
+        Ok(())
+    }
 }
 
-/// Build a query string from properties, properties_with_history, associations, and archived
-pub fn build_query_string(
-    query_already_begun: bool,
-    properties: &[&str],
-    properties_with_history: &[&str],
-    associations: &[&str],
-    archived: bool,
-) -> String {
-    let mut query_begun = query_already_begun;
-
-    let property_query = if properties.is_empty() {
-        String::new()
-    } else {
-        let query_check = query_begun_check(query_begun);
-        query_begun = query_check.1;
-        format!("{}properties={}", query_check.0, properties.join(","))
-    };
-    let properties_with_history_query = if properties_with_history.is_empty() {
-        String::new()
-    } else {
-        let query_check = query_begun_check(query_begun);
-        query_begun = query_check.1;
-        format!(
-            "{}propertiesWithHistory={}",
-            query_check.0,
-            properties_with_history.join(",")
-        )
-    };
-    let associations_query = if associations.is_empty() {
-        String::new()
-    } else {
-        let query_check = query_begun_check(query_begun);
-        query_begun = query_check.1;
-        format!("{}associations={}", query_check.0, associations.join(","))
-    };
-    let archived_query = if query_begun {
-        format!("&archived={}", archived)
-    } else {
-        format!("?archived={}", archived)
-    };
-
-    format!("{property_query}{properties_with_history_query}{associations_query}{archived_query}")
+/// Percent-encode a value for use as a single path segment, eg. an email used as a record ID.
+pub(crate) fn encode_path_segment(segment: &str) -> String {
+    utf8_percent_encode(segment, QUERY_ENCODE_SET).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_query_displays_nothing() {
+        assert_eq!(Query::new().to_string(), "");
+    }
+
+    #[test]
+    fn encodes_reserved_characters() {
+        let query = Query::new()
+            .push("idProperty", "email")
+            .push("after", "a+b/c=")
+            .push("email", "jane doe@example.com");
+
+        assert_eq!(
+            query.to_string(),
+            "?idProperty=email&after=a%2Bb%2Fc%3D&email=jane%20doe%40example.com"
+        );
+    }
+
+    #[test]
+    fn repeats_parameters() {
+        let query = Query::new()
+            .push_all("properties", ["firstname", "last name"])
+            .push_opt("limit", Some(10))
+            .push_opt::<i32>("after", None);
+
+        assert_eq!(
+            query.to_string(),
+            "?properties=firstname&properties=last%20name&limit=10"
+        );
+    }
+
+    #[test]
+    fn encodes_path_segments() {
+        assert_eq!(encode_path_segment("a+b@c.com"), "a%2Bb%40c.com");
+        assert_eq!(encode_path_segment("x/y z"), "x%2Fy%20z");
+    }
 }
@@ -0,0 +1,343 @@
+use serde::{Deserialize, Serialize};
+
+use super::types::Paging;
+
+/// The operators a search filter can compare a property with.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FilterOperator {
+    /// Equal to the value.
+    Eq,
+    /// Not equal to the value.
+    Neq,
+    /// Less than the value.
+    Lt,
+    /// Less than or equal to the value.
+    Lte,
+    /// Greater than the value.
+    Gt,
+    /// Greater than or equal to the value.
+    Gte,
+    /// Within the inclusive range of the value and high value.
+    Between,
+    /// Equal to one of the values.
+    In,
+    /// Not equal to any of the values.
+    NotIn,
+    /// The record has a value for the property.
+    HasProperty,
+    /// The record has no value for the property.
+    NotHasProperty,
+    /// Contains the value as a token, supporting `*` wildcards.
+    ContainsToken,
+    /// Doesn't contain the value as a token.
+    NotContainsToken,
+}
+
+/// A condition on a single property.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Filter {
+    /// The property to compare.
+    pub property_name: String,
+    /// How the property is compared.
+    pub operator: FilterOperator,
+    /// The value to compare with.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// The upper bound of a [FilterOperator::Between] filter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub high_value: Option<String>,
+    /// The values of an [FilterOperator::In] or [FilterOperator::NotIn] filter.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<String>,
+}
+
+impl Filter {
+    /// Create a filter comparing a property with a single value.
+    pub fn new(property_name: &str, operator: FilterOperator, value: Option<String>) -> Self {
+        Self {
+            property_name: property_name.to_owned(),
+            operator,
+            value,
+            high_value: None,
+            values: Vec::new(),
+        }
+    }
+
+    /// The property is equal to the value.
+    pub fn eq(property_name: &str, value: impl ToString) -> Self {
+        Self::new(property_name, FilterOperator::Eq, Some(value.to_string()))
+    }
+
+    /// The property is not equal to the value.
+    pub fn neq(property_name: &str, value: impl ToString) -> Self {
+        Self::new(property_name, FilterOperator::Neq, Some(value.to_string()))
+    }
+
+    /// The property is less than the value.
+    pub fn lt(property_name: &str, value: impl ToString) -> Self {
+        Self::new(property_name, FilterOperator::Lt, Some(value.to_string()))
+    }
+
+    /// The property is less than or equal to the value.
+    pub fn lte(property_name: &str, value: impl ToString) -> Self {
+        Self::new(property_name, FilterOperator::Lte, Some(value.to_string()))
+    }
+
+    /// The property is greater than the value.
+    pub fn gt(property_name: &str, value: impl ToString) -> Self {
+        Self::new(property_name, FilterOperator::Gt, Some(value.to_string()))
+    }
+
+    /// The property is greater than or equal to the value.
+    pub fn gte(property_name: &str, value: impl ToString) -> Self {
+        Self::new(property_name, FilterOperator::Gte, Some(value.to_string()))
+    }
+
+    /// The property is within the inclusive range of the values.
+    pub fn between(property_name: &str, value: impl ToString, high_value: impl ToString) -> Self {
+        Self {
+            high_value: Some(high_value.to_string()),
+            ..Self::new(
+                property_name,
+                FilterOperator::Between,
+                Some(value.to_string()),
+            )
+        }
+    }
+
+    /// The property is equal to one of the values.
+    pub fn is_in<V: ToString>(property_name: &str, values: impl IntoIterator<Item = V>) -> Self {
+        Self {
+            values: values.into_iter().map(|v| v.to_string()).collect(),
+            ..Self::new(property_name, FilterOperator::In, None)
+        }
+    }
+
+    /// The property is not equal to any of the values.
+    pub fn not_in<V: ToString>(property_name: &str, values: impl IntoIterator<Item = V>) -> Self {
+        Self {
+            values: values.into_iter().map(|v| v.to_string()).collect(),
+            ..Self::new(property_name, FilterOperator::NotIn, None)
+        }
+    }
+
+    /// The record has a value for the property.
+    pub fn has_property(property_name: &str) -> Self {
+        Self::new(property_name, FilterOperator::HasProperty, None)
+    }
+
+    /// The record has no value for the property.
+    pub fn not_has_property(property_name: &str) -> Self {
+        Self::new(property_name, FilterOperator::NotHasProperty, None)
+    }
+
+    /// The property contains the token. Supports `*` wildcards, eg. `*@example.com`.
+    pub fn contains_token(property_name: &str, value: impl ToString) -> Self {
+        Self::new(
+            property_name,
+            FilterOperator::ContainsToken,
+            Some(value.to_string()),
+        )
+    }
+
+    /// The property doesn't contain the token.
+    pub fn not_contains_token(property_name: &str, value: impl ToString) -> Self {
+        Self::new(
+            property_name,
+            FilterOperator::NotContainsToken,
+            Some(value.to_string()),
+        )
+    }
+}
+
+/// A group of filters a record must all match.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct FilterGroup {
+    /// The filters of the group.
+    pub filters: Vec<Filter>,
+}
+
+impl FilterGroup {
+    /// Create an empty filter group.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Add a filter the records must match.
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+}
+
+/// The direction to sort search results in.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+/// The property to sort search results by.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Sort {
+    /// The property to sort by.
+    pub property_name: String,
+    /// The direction to sort in.
+    pub direction: SortDirection,
+}
+
+/// A search for records of an object type.
+///
+/// Records must match all the filters of any one of the filter groups.
+#[derive(Serialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchRequest {
+    filter_groups: Vec<FilterGroup>,
+    sorts: Vec<Sort>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    query: Option<String>,
+    /// The properties to return, set from the requested properties type.
+    pub(crate) properties: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limit: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    after: Option<String>,
+}
+
+impl SearchRequest {
+    /// Create a search matching every record.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Add a group of filters. Records matching any of the groups are returned.
+    pub fn filter_group(mut self, filter_group: FilterGroup) -> Self {
+        self.filter_groups.push(filter_group);
+        self
+    }
+
+    /// Sort the results by a property.
+    pub fn sort(mut self, property_name: &str, direction: SortDirection) -> Self {
+        self.sorts.push(Sort {
+            property_name: property_name.to_owned(),
+            direction,
+        });
+        self
+    }
+
+    /// Search the default text properties of the records, eg. a contact's name and email.
+    pub fn query(mut self, query: &str) -> Self {
+        self.query = Some(query.to_owned());
+        self
+    }
+
+    /// The maximum number of results to return per page.
+    pub fn limit(mut self, limit: i32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// The paging cursor of the page to return, as returned in `paging.next.after`.
+    pub fn after(mut self, after: &str) -> Self {
+        self.after = Some(after.to_owned());
+        self
+    }
+}
+
+/// A page of search results.
+#[derive(Deserialize, Debug, Default)]
+pub struct SearchResult<T> {
+    /// The total number of records matching the search.
+    pub total: i64,
+    /// A Vec of the results.
+    pub results: Vec<T>,
+    /// Paging information.
+    pub paging: Option<Paging>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn serializes_the_search_body() {
+        let request = SearchRequest::new()
+            .filter_group(
+                FilterGroup::new()
+                    .filter(Filter::eq("lifecyclestage", "customer"))
+                    .filter(Filter::between("createdate", 1, 2)),
+            )
+            .filter_group(
+                FilterGroup::new().filter(Filter::is_in("hs_lead_status", ["NEW", "OPEN"])),
+            )
+            .sort("createdate", SortDirection::Descending)
+            .limit(10)
+            .after("20");
+
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({
+                "filterGroups": [
+                    {
+                        "filters": [
+                            { "propertyName": "lifecyclestage", "operator": "EQ", "value": "customer" },
+                            { "propertyName": "createdate", "operator": "BETWEEN", "value": "1", "highValue": "2" },
+                        ]
+                    },
+                    {
+                        "filters": [
+                            { "propertyName": "hs_lead_status", "operator": "IN", "values": ["NEW", "OPEN"] },
+                        ]
+                    },
+                ],
+                "sorts": [{ "propertyName": "createdate", "direction": "DESCENDING" }],
+                "properties": [],
+                "limit": 10,
+                "after": "20",
+            })
+        );
+    }
+
+    #[test]
+    fn omits_unset_values() {
+        assert_eq!(
+            serde_json::to_value(Filter::has_property("email")).unwrap(),
+            json!({ "propertyName": "email", "operator": "HAS_PROPERTY" })
+        );
+        assert_eq!(
+            serde_json::to_value(SearchRequest::new().query("hubspot")).unwrap(),
+            json!({ "filterGroups": [], "sorts": [], "query": "hubspot", "properties": [] })
+        );
+    }
+
+    #[test]
+    fn serializes_operators_in_screaming_snake_case() {
+        assert_eq!(
+            serde_json::to_value(FilterOperator::NotContainsToken).unwrap(),
+            json!("NOT_CONTAINS_TOKEN")
+        );
+        assert_eq!(
+            serde_json::to_value(FilterOperator::NotHasProperty).unwrap(),
+            json!("NOT_HAS_PROPERTY")
+        );
+    }
+
+    #[test]
+    fn deserializes_search_results() {
+        let result: SearchResult<serde_json::Value> = serde_json::from_value(json!({
+            "total": 3,
+            "results": [{ "id": "1" }],
+            "paging": { "next": { "after": "1" } },
+        }))
+        .unwrap();
+
+        assert_eq!(result.total, 3);
+        assert_eq!(result.paging.unwrap().next.after, "1");
+    }
+}
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
 
+use crate::client::error::{HubspotError, HubspotResult};
 use crate::client::HubspotClient;
 
 /// ToPath trait represents a Hubspot object's path.
@@ -156,6 +159,16 @@ pub struct OptionNotDesired {}
 #[derive(Serialize, Deserialize, Debug, Default)]
 pub struct AssociationResults {
     pub results: Vec<Association>,
+    /// Paging information, when the record has more associations than were returned with it.
+    #[serde(default)]
+    pub paging: Option<Paging>,
+}
+
+impl AssociationResults {
+    /// Whether every association of the record was returned with it.
+    pub fn is_complete(&self) -> bool {
+        self.paging.is_none()
+    }
 }
 
 /// An representation of an association as returned by Hubspot
@@ -166,6 +179,22 @@ pub struct Association {
     pub association_type: String,
 }
 
+impl Association {
+    /// The Hubspot defined association type, if the type is one of [AssociationLinks].
+    pub fn link(&self) -> Option<AssociationLinks> {
+        AssociationLinks::from_type_name(&self.association_type)
+    }
+
+    /// The numeric ID of the association type.
+    ///
+    /// Hubspot defined types are returned by name, eg. `contact_to_company`, and others by ID.
+    pub fn type_id(&self) -> Option<i64> {
+        self.link()
+            .map(|link| link.type_id())
+            .or_else(|| self.association_type.parse().ok())
+    }
+}
+
 /// A paged result type.
 #[derive(Deserialize, Debug, Default)]
 pub struct ListResult<T> {
@@ -176,24 +205,99 @@ pub struct ListResult<T> {
 }
 
 /// Paging information
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct Paging {
     /// The next page
     pub next: PagingNext,
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct PagingNext {
     pub after: String,
+    #[serde(default)]
     pub link: String,
 }
 
-/// An enum of Built In Hubspot Associations.
-/// To be built upon in the future.
+/// The association types defined by Hubspot between the standard objects and engagements.
+///
+/// Each link is one direction of an association; the reverse direction has its own link and type ID.
+/// `Primary` links mark a record's primary company.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter)]
 pub enum AssociationLinks {
+    ContactToCompany,
+    ContactToCompanyPrimary,
+    CompanyToContact,
+    CompanyToContactPrimary,
+    DealToContact,
+    ContactToDeal,
+    DealToCompany,
+    DealToCompanyPrimary,
+    CompanyToDeal,
+    CompanyToDealPrimary,
+    CompanyToTicket,
+    CompanyToTicketPrimary,
+    TicketToCompany,
+    TicketToCompanyPrimary,
+    ContactToTicket,
+    TicketToContact,
+    DealToTicket,
+    TicketToDeal,
+    DealToLineItem,
+    LineItemToDeal,
+    ParentCompanyToChildCompany,
+    ChildCompanyToParentCompany,
+    ContactToContact,
+    CompanyToCompany,
+    DealToDeal,
+    TicketToTicket,
+    CallToContact,
+    CallToCompany,
+    CallToDeal,
+    CallToTicket,
+    ContactToCall,
+    CompanyToCall,
+    DealToCall,
+    TicketToCall,
+    EmailToContact,
+    EmailToCompany,
+    EmailToDeal,
+    EmailToTicket,
+    ContactToEmail,
+    CompanyToEmail,
+    DealToEmail,
+    TicketToEmail,
+    MeetingToContact,
+    MeetingToCompany,
+    MeetingToDeal,
+    MeetingToTicket,
+    ContactToMeeting,
+    CompanyToMeeting,
+    DealToMeeting,
+    TicketToMeeting,
     NoteToContact,
     NoteToCompany,
     NoteToDeal,
+    NoteToTicket,
+    ContactToNote,
+    CompanyToNote,
+    DealToNote,
+    TicketToNote,
+    TaskToContact,
+    TaskToCompany,
+    TaskToDeal,
+    TaskToTicket,
+    ContactToTask,
+    CompanyToTask,
+    DealToTask,
+    TicketToTask,
+    CommunicationToContact,
+    CommunicationToCompany,
+    CommunicationToDeal,
+    CommunicationToTicket,
+    ContactToCommunication,
+    CompanyToCommunication,
+    DealToCommunication,
+    TicketToCommunication,
 }
 
 /// Implementation of CreateAssociation
@@ -216,14 +320,289 @@ impl CreateAssociation {
 
 /// Implementation of AssociationLinks
 impl AssociationLinks {
+    /// The source object path, target object path, type ID and type name of the link.
+    fn parts(&self) -> (&'static str, &'static str, i64, &'static str) {
+        match self {
+            AssociationLinks::ContactToCompany => {
+                ("contacts", "companies", 279, "contact_to_company_unlabeled")
+            }
+            AssociationLinks::ContactToCompanyPrimary => {
+                ("contacts", "companies", 1, "contact_to_company")
+            }
+            AssociationLinks::CompanyToContact => {
+                ("companies", "contacts", 280, "company_to_contact_unlabeled")
+            }
+            AssociationLinks::CompanyToContactPrimary => {
+                ("companies", "contacts", 2, "company_to_contact")
+            }
+            AssociationLinks::DealToContact => ("deals", "contacts", 3, "deal_to_contact"),
+            AssociationLinks::ContactToDeal => ("contacts", "deals", 4, "contact_to_deal"),
+            AssociationLinks::DealToCompany => {
+                ("deals", "companies", 341, "deal_to_company_unlabeled")
+            }
+            AssociationLinks::DealToCompanyPrimary => ("deals", "companies", 5, "deal_to_company"),
+            AssociationLinks::CompanyToDeal => {
+                ("companies", "deals", 342, "company_to_deal_unlabeled")
+            }
+            AssociationLinks::CompanyToDealPrimary => ("companies", "deals", 6, "company_to_deal"),
+            AssociationLinks::CompanyToTicket => {
+                ("companies", "tickets", 340, "company_to_ticket_unlabeled")
+            }
+            AssociationLinks::CompanyToTicketPrimary => {
+                ("companies", "tickets", 25, "company_to_ticket")
+            }
+            AssociationLinks::TicketToCompany => {
+                ("tickets", "companies", 339, "ticket_to_company_unlabeled")
+            }
+            AssociationLinks::TicketToCompanyPrimary => {
+                ("tickets", "companies", 26, "ticket_to_company")
+            }
+            AssociationLinks::ContactToTicket => ("contacts", "tickets", 15, "contact_to_ticket"),
+            AssociationLinks::TicketToContact => ("tickets", "contacts", 16, "ticket_to_contact"),
+            AssociationLinks::DealToTicket => ("deals", "tickets", 27, "deal_to_ticket"),
+            AssociationLinks::TicketToDeal => ("tickets", "deals", 28, "ticket_to_deal"),
+            AssociationLinks::DealToLineItem => ("deals", "line_items", 19, "deal_to_line_item"),
+            AssociationLinks::LineItemToDeal => ("line_items", "deals", 20, "line_item_to_deal"),
+            AssociationLinks::ParentCompanyToChildCompany => {
+                ("companies", "companies", 13, "parent_to_child_company")
+            }
+            AssociationLinks::ChildCompanyToParentCompany => {
+                ("companies", "companies", 14, "child_to_parent_company")
+            }
+            AssociationLinks::ContactToContact => {
+                ("contacts", "contacts", 449, "contact_to_contact")
+            }
+            AssociationLinks::CompanyToCompany => {
+                ("companies", "companies", 450, "company_to_company")
+            }
+            AssociationLinks::DealToDeal => ("deals", "deals", 451, "deal_to_deal"),
+            AssociationLinks::TicketToTicket => ("tickets", "tickets", 452, "ticket_to_ticket"),
+            AssociationLinks::CallToContact => ("calls", "contacts", 194, "call_to_contact"),
+            AssociationLinks::CallToCompany => ("calls", "companies", 182, "call_to_company"),
+            AssociationLinks::CallToDeal => ("calls", "deals", 206, "call_to_deal"),
+            AssociationLinks::CallToTicket => ("calls", "tickets", 220, "call_to_ticket"),
+            AssociationLinks::ContactToCall => ("contacts", "calls", 193, "contact_to_call"),
+            AssociationLinks::CompanyToCall => ("companies", "calls", 181, "company_to_call"),
+            AssociationLinks::DealToCall => ("deals", "calls", 205, "deal_to_call"),
+            AssociationLinks::TicketToCall => ("tickets", "calls", 219, "ticket_to_call"),
+            AssociationLinks::EmailToContact => ("emails", "contacts", 198, "email_to_contact"),
+            AssociationLinks::EmailToCompany => ("emails", "companies", 186, "email_to_company"),
+            AssociationLinks::EmailToDeal => ("emails", "deals", 210, "email_to_deal"),
+            AssociationLinks::EmailToTicket => ("emails", "tickets", 224, "email_to_ticket"),
+            AssociationLinks::ContactToEmail => ("contacts", "emails", 197, "contact_to_email"),
+            AssociationLinks::CompanyToEmail => ("companies", "emails", 185, "company_to_email"),
+            AssociationLinks::DealToEmail => ("deals", "emails", 209, "deal_to_email"),
+            AssociationLinks::TicketToEmail => ("tickets", "emails", 223, "ticket_to_email"),
+            AssociationLinks::MeetingToContact => {
+                ("meetings", "contacts", 200, "meeting_to_contact")
+            }
+            AssociationLinks::MeetingToCompany => {
+                ("meetings", "companies", 188, "meeting_to_company")
+            }
+            AssociationLinks::MeetingToDeal => ("meetings", "deals", 212, "meeting_to_deal"),
+            AssociationLinks::MeetingToTicket => ("meetings", "tickets", 226, "meeting_to_ticket"),
+            AssociationLinks::ContactToMeeting => {
+                ("contacts", "meetings", 199, "contact_to_meeting")
+            }
+            AssociationLinks::CompanyToMeeting => {
+                ("companies", "meetings", 187, "company_to_meeting")
+            }
+            AssociationLinks::DealToMeeting => ("deals", "meetings", 211, "deal_to_meeting"),
+            AssociationLinks::TicketToMeeting => ("tickets", "meetings", 225, "ticket_to_meeting"),
+            AssociationLinks::NoteToContact => ("notes", "contacts", 202, "note_to_contact"),
+            AssociationLinks::NoteToCompany => ("notes", "companies", 190, "note_to_company"),
+            AssociationLinks::NoteToDeal => ("notes", "deals", 214, "note_to_deal"),
+            AssociationLinks::NoteToTicket => ("notes", "tickets", 228, "note_to_ticket"),
+            AssociationLinks::ContactToNote => ("contacts", "notes", 201, "contact_to_note"),
+            AssociationLinks::CompanyToNote => ("companies", "notes", 189, "company_to_note"),
+            AssociationLinks::DealToNote => ("deals", "notes", 213, "deal_to_note"),
+            AssociationLinks::TicketToNote => ("tickets", "notes", 227, "ticket_to_note"),
+            AssociationLinks::TaskToContact => ("tasks", "contacts", 204, "task_to_contact"),
+            AssociationLinks::TaskToCompany => ("tasks", "companies", 192, "task_to_company"),
+            AssociationLinks::TaskToDeal => ("tasks", "deals", 216, "task_to_deal"),
+            AssociationLinks::TaskToTicket => ("tasks", "tickets", 230, "task_to_ticket"),
+            AssociationLinks::ContactToTask => ("contacts", "tasks", 203, "contact_to_task"),
+            AssociationLinks::CompanyToTask => ("companies", "tasks", 191, "company_to_task"),
+            AssociationLinks::DealToTask => ("deals", "tasks", 215, "deal_to_task"),
+            AssociationLinks::TicketToTask => ("tickets", "tasks", 229, "ticket_to_task"),
+            AssociationLinks::CommunicationToContact => {
+                ("communications", "contacts", 82, "communication_to_contact")
+            }
+            AssociationLinks::CommunicationToCompany => (
+                "communications",
+                "companies",
+                88,
+                "communication_to_company",
+            ),
+            AssociationLinks::CommunicationToDeal => {
+                ("communications", "deals", 86, "communication_to_deal")
+            }
+            AssociationLinks::CommunicationToTicket => {
+                ("communications", "tickets", 84, "communication_to_ticket")
+            }
+            AssociationLinks::ContactToCommunication => {
+                ("contacts", "communications", 81, "contact_to_communication")
+            }
+            AssociationLinks::CompanyToCommunication => (
+                "companies",
+                "communications",
+                87,
+                "company_to_communication",
+            ),
+            AssociationLinks::DealToCommunication => {
+                ("deals", "communications", 85, "deal_to_communication")
+            }
+            AssociationLinks::TicketToCommunication => {
+                ("tickets", "communications", 83, "ticket_to_communication")
+            }
+        }
+    }
+
+    /// The path of the object type the association is from, eg. `contacts`.
+    pub fn source_path(&self) -> &'static str {
+        self.parts().0
+    }
+
+    /// The path of the object type the association is to, eg. `companies`.
+    pub fn target_path(&self) -> &'static str {
+        self.parts().1
+    }
+
+    /// The numeric ID of the association type.
+    pub fn type_id(&self) -> i64 {
+        self.parts().2
+    }
+
+    /// The name Hubspot gives the association type in the associations of a record,
+    /// eg. `contact_to_company` for the primary company and `contact_to_company_unlabeled` otherwise.
+    pub fn type_name(&self) -> &'static str {
+        self.parts().3
+    }
+
+    /// Returns the link with the given [AssociationLinks::type_name], if there is one.
+    pub fn from_type_name(name: &str) -> Option<Self> {
+        match name {
+            "contact_to_company_unlabeled" => Some(AssociationLinks::ContactToCompany),
+            "contact_to_company" => Some(AssociationLinks::ContactToCompanyPrimary),
+            "company_to_contact_unlabeled" => Some(AssociationLinks::CompanyToContact),
+            "company_to_contact" => Some(AssociationLinks::CompanyToContactPrimary),
+            "deal_to_contact" => Some(AssociationLinks::DealToContact),
+            "contact_to_deal" => Some(AssociationLinks::ContactToDeal),
+            "deal_to_company_unlabeled" => Some(AssociationLinks::DealToCompany),
+            "deal_to_company" => Some(AssociationLinks::DealToCompanyPrimary),
+            "company_to_deal_unlabeled" => Some(AssociationLinks::CompanyToDeal),
+            "company_to_deal" => Some(AssociationLinks::CompanyToDealPrimary),
+            "company_to_ticket_unlabeled" => Some(AssociationLinks::CompanyToTicket),
+            "company_to_ticket" => Some(AssociationLinks::CompanyToTicketPrimary),
+            "ticket_to_company_unlabeled" => Some(AssociationLinks::TicketToCompany),
+            "ticket_to_company" => Some(AssociationLinks::TicketToCompanyPrimary),
+            "contact_to_ticket" => Some(AssociationLinks::ContactToTicket),
+            "ticket_to_contact" => Some(AssociationLinks::TicketToContact),
+            "deal_to_ticket" => Some(AssociationLinks::DealToTicket),
+            "ticket_to_deal" => Some(AssociationLinks::TicketToDeal),
+            "deal_to_line_item" => Some(AssociationLinks::DealToLineItem),
+            "line_item_to_deal" => Some(AssociationLinks::LineItemToDeal),
+            "parent_to_child_company" => Some(AssociationLinks::ParentCompanyToChildCompany),
+            "child_to_parent_company" => Some(AssociationLinks::ChildCompanyToParentCompany),
+            "contact_to_contact" => Some(AssociationLinks::ContactToContact),
+            "company_to_company" => Some(AssociationLinks::CompanyToCompany),
+            "deal_to_deal" => Some(AssociationLinks::DealToDeal),
+            "ticket_to_ticket" => Some(AssociationLinks::TicketToTicket),
+            "call_to_contact" => Some(AssociationLinks::CallToContact),
+            "call_to_company" => Some(AssociationLinks::CallToCompany),
+            "call_to_deal" => Some(AssociationLinks::CallToDeal),
+            "call_to_ticket" => Some(AssociationLinks::CallToTicket),
+            "contact_to_call" => Some(AssociationLinks::ContactToCall),
+            "company_to_call" => Some(AssociationLinks::CompanyToCall),
+            "deal_to_call" => Some(AssociationLinks::DealToCall),
+            "ticket_to_call" => Some(AssociationLinks::TicketToCall),
+            "email_to_contact" => Some(AssociationLinks::EmailToContact),
+            "email_to_company" => Some(AssociationLinks::EmailToCompany),
+            "email_to_deal" => Some(AssociationLinks::EmailToDeal),
+            "email_to_ticket" => Some(AssociationLinks::EmailToTicket),
+            "contact_to_email" => Some(AssociationLinks::ContactToEmail),
+            "company_to_email" => Some(AssociationLinks::CompanyToEmail),
+            "deal_to_email" => Some(AssociationLinks::DealToEmail),
+            "ticket_to_email" => Some(AssociationLinks::TicketToEmail),
+            "meeting_to_contact" => Some(AssociationLinks::MeetingToContact),
+            "meeting_to_company" => Some(AssociationLinks::MeetingToCompany),
+            "meeting_to_deal" => Some(AssociationLinks::MeetingToDeal),
+            "meeting_to_ticket" => Some(AssociationLinks::MeetingToTicket),
+            "contact_to_meeting" => Some(AssociationLinks::ContactToMeeting),
+            "company_to_meeting" => Some(AssociationLinks::CompanyToMeeting),
+            "deal_to_meeting" => Some(AssociationLinks::DealToMeeting),
+            "ticket_to_meeting" => Some(AssociationLinks::TicketToMeeting),
+            "note_to_contact" => Some(AssociationLinks::NoteToContact),
+            "note_to_company" => Some(AssociationLinks::NoteToCompany),
+            "note_to_deal" => Some(AssociationLinks::NoteToDeal),
+            "note_to_ticket" => Some(AssociationLinks::NoteToTicket),
+            "contact_to_note" => Some(AssociationLinks::ContactToNote),
+            "company_to_note" => Some(AssociationLinks::CompanyToNote),
+            "deal_to_note" => Some(AssociationLinks::DealToNote),
+            "ticket_to_note" => Some(AssociationLinks::TicketToNote),
+            "task_to_contact" => Some(AssociationLinks::TaskToContact),
+            "task_to_company" => Some(AssociationLinks::TaskToCompany),
+            "task_to_deal" => Some(AssociationLinks::TaskToDeal),
+            "task_to_ticket" => Some(AssociationLinks::TaskToTicket),
+            "contact_to_task" => Some(AssociationLinks::ContactToTask),
+            "company_to_task" => Some(AssociationLinks::CompanyToTask),
+            "deal_to_task" => Some(AssociationLinks::DealToTask),
+            "ticket_to_task" => Some(AssociationLinks::TicketToTask),
+            "communication_to_contact" => Some(AssociationLinks::CommunicationToContact),
+            "communication_to_company" => Some(AssociationLinks::CommunicationToCompany),
+            "communication_to_deal" => Some(AssociationLinks::CommunicationToDeal),
+            "communication_to_ticket" => Some(AssociationLinks::CommunicationToTicket),
+            "contact_to_communication" => Some(AssociationLinks::ContactToCommunication),
+            "company_to_communication" => Some(AssociationLinks::CompanyToCommunication),
+            "deal_to_communication" => Some(AssociationLinks::DealToCommunication),
+            "ticket_to_communication" => Some(AssociationLinks::TicketToCommunication),
+            _ => None,
+        }
+    }
+
+    /// Whether the link marks the primary company of a record.
+    pub fn is_primary(&self) -> bool {
+        matches!(
+            self,
+            AssociationLinks::ContactToCompanyPrimary
+                | AssociationLinks::CompanyToContactPrimary
+                | AssociationLinks::DealToCompanyPrimary
+                | AssociationLinks::CompanyToDealPrimary
+                | AssociationLinks::CompanyToTicketPrimary
+                | AssociationLinks::TicketToCompanyPrimary
+        )
+    }
+
+    /// Returns the default, unlabeled link from one object type to another,
+    /// eg. `AssociationLinks::between(&ObjectType::Deals, &ObjectType::LineItems)`.
+    ///
+    /// Returns [HubspotError::Association] if Hubspot defines no association between the object types.
+    /// Between companies, this is the unlabeled [AssociationLinks::CompanyToCompany].
+    pub fn between(from: &impl ToPath, to: &impl ToPath) -> HubspotResult<Self> {
+        let (from, to) = (from.to_path(), to.to_path());
+        AssociationLinks::iter()
+            .find(|link| {
+                link.source_path() == from
+                    && link.target_path() == to
+                    && !link.is_primary()
+                    && !matches!(
+                        link,
+                        AssociationLinks::ParentCompanyToChildCompany
+                            | AssociationLinks::ChildCompanyToParentCompany
+                    )
+            })
+            .ok_or_else(|| {
+                HubspotError::Association(format!(
+                    "Hubspot defines no association from {} to {}",
+                    from, to
+                ))
+            })
+    }
+
     /// Build a new AssociationType from the given AssociationLinks
     pub fn build(&self) -> AssociationType {
         AssociationType {
-            id: match self {
-                AssociationLinks::NoteToContact => "202".to_string(),
-                AssociationLinks::NoteToCompany => "190".to_string(),
-                AssociationLinks::NoteToDeal => "214".to_string(),
-            },
+            id: self.type_id().to_string(),
             category: "HUBSPOT_DEFINED".to_string(),
         }
     }
@@ -239,3 +618,110 @@ impl AssociationType {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::objects::ObjectType;
+
+    #[test]
+    fn looks_links_up_by_type_name() {
+        let mut names = HashSet::new();
+        for link in AssociationLinks::iter() {
+            assert!(names.insert(link.type_name()), "{link:?}");
+            assert_eq!(
+                AssociationLinks::from_type_name(link.type_name()),
+                Some(link)
+            );
+        }
+
+        assert_eq!(AssociationLinks::from_type_name("contact_to_nothing"), None);
+    }
+
+    #[test]
+    fn defines_links_in_both_directions() {
+        let pairs = AssociationLinks::iter()
+            .map(|link| (link.source_path(), link.target_path()))
+            .collect::<HashSet<_>>();
+
+        for (source, target) in &pairs {
+            assert!(pairs.contains(&(target, source)), "{source} to {target}");
+        }
+    }
+
+    #[test]
+    fn returns_the_unlabeled_link_between_object_types() {
+        let link = AssociationLinks::between(&ObjectType::Deals, &ObjectType::LineItems).unwrap();
+        assert_eq!(link, AssociationLinks::DealToLineItem);
+        assert_eq!(link.type_id(), 19);
+
+        assert_eq!(
+            AssociationLinks::between(&ObjectType::Contacts, &ObjectType::Companies).unwrap(),
+            AssociationLinks::ContactToCompany
+        );
+        assert_eq!(
+            AssociationLinks::between(&ObjectType::Companies, &ObjectType::Companies).unwrap(),
+            AssociationLinks::CompanyToCompany
+        );
+        assert_eq!(
+            AssociationLinks::between(
+                &ObjectType::Custom("calls".to_owned()),
+                &ObjectType::Tickets
+            )
+            .unwrap(),
+            AssociationLinks::CallToTicket
+        );
+    }
+
+    #[test]
+    fn keeps_the_paging_of_associations_read_with_a_record() {
+        let paged: AssociationResults = serde_json::from_value(serde_json::json!({
+            "results": [{ "id": "7", "type": "contact_to_company" }],
+            "paging": { "next": { "after": "7" } }
+        }))
+        .unwrap();
+        let complete: AssociationResults = serde_json::from_value(serde_json::json!({
+            "results": [{ "id": "7", "type": "contact_to_company" }]
+        }))
+        .unwrap();
+
+        assert!(!paged.is_complete());
+        assert_eq!(paged.paging.unwrap().next.after, "7");
+        assert!(complete.is_complete());
+    }
+
+    #[test]
+    fn reads_the_type_of_an_association() {
+        let defined = Association {
+            id: "7".to_owned(),
+            association_type: "contact_to_company".to_owned(),
+        };
+        let labeled = Association {
+            id: "7".to_owned(),
+            association_type: "5".to_owned(),
+        };
+        let unknown = Association {
+            id: "7".to_owned(),
+            association_type: "contact_to_nothing".to_owned(),
+        };
+
+        assert_eq!(
+            defined.link(),
+            Some(AssociationLinks::ContactToCompanyPrimary)
+        );
+        assert_eq!(defined.type_id(), Some(1));
+        assert_eq!(labeled.link(), None);
+        assert_eq!(labeled.type_id(), Some(5));
+        assert_eq!(unknown.type_id(), None);
+    }
+
+    #[test]
+    fn fails_for_object_types_without_a_link() {
+        let error =
+            AssociationLinks::between(&ObjectType::LineItems, &ObjectType::Tickets).unwrap_err();
+
+        assert!(matches!(error, HubspotError::Association(_)));
+    }
+}
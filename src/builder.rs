@@ -1,19 +1,46 @@
 use std::error::Error;
 use std::fmt::{Display, Formatter};
+use std::sync::Arc;
 
-use reqwest::Client;
+use reqwest::{Client, Url};
 
 use crate::Hubspot;
 
-use super::client::HubspotClient;
+use super::client::{
+    oauth::OAuth, rate_limit::RateLimit, retry::RetryPolicy, transport::HttpTransport, Auth,
+    HubspotClient,
+};
+
+/// The Hubspot data centers hosting the api.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Region {
+    /// The standard api host, `api.hubapi.com`.
+    Standard,
+    /// The EU data center, `api-eu1.hubapi.com`.
+    Eu1,
+}
+
+impl Region {
+    /// The base url of the region's api.
+    pub fn base_url(&self) -> &'static str {
+        match self {
+            Region::Standard => "https://api.hubapi.com",
+            Region::Eu1 => "https://api-eu1.hubapi.com",
+        }
+    }
+}
 
 /// Hubspot api  interface.
 #[derive(Default)]
 pub struct HubspotBuilder {
     client: Option<Client>,
-    domain: Option<String>,
+    transport: Option<Arc<dyn HttpTransport>>,
+    base_url: Option<String>,
     token: Option<String>,
+    oauth: Option<OAuth>,
     portal_id: Option<String>,
+    retry_policy: Option<RetryPolicy>,
+    rate_limit: Option<RateLimit>,
 }
 
 impl HubspotBuilder {
@@ -26,14 +53,23 @@ impl HubspotBuilder {
     ///
     /// Valid builder options requires all fields to be populated.
     pub fn build(&self) -> Result<Hubspot, HubspotBuilderError> {
-        let domain = self
-            .domain
+        let base_url = self
+            .base_url
             .as_ref()
             .ok_or(HubspotBuilderError::MissingDomain)?;
-        let token = self
-            .token
-            .as_ref()
-            .ok_or(HubspotBuilderError::MissingToken)?;
+        match Url::parse(base_url) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => {}
+            _ => return Err(HubspotBuilderError::InvalidBaseUrl),
+        }
+        let auth = match (&self.oauth, &self.token) {
+            (Some(oauth), _) => Auth::OAuth(
+                oauth
+                    .clone()
+                    .with_defaults(base_url, self.transport.as_ref()),
+            ),
+            (None, Some(token)) => Auth::Token(token.clone()),
+            (None, None) => return Err(HubspotBuilderError::MissingToken),
+        };
         let portal_id = self
             .portal_id
             .as_ref()
@@ -43,14 +79,32 @@ impl HubspotBuilder {
             None => Client::new(),
         };
 
-        let client = HubspotClient::new(client, domain, token, portal_id);
+        let mut client = HubspotClient::new(client, base_url, auth, portal_id)
+            .with_retry_policy(self.retry_policy.clone().unwrap_or_else(RetryPolicy::none))
+            .with_rate_limit(self.rate_limit.clone().unwrap_or_else(RateLimit::disabled));
+        if let Some(transport) = &self.transport {
+            client = client.with_transport(Arc::clone(transport));
+        }
 
         Ok(Hubspot::new(client))
     }
 
-    /// The hubspot domain.
+    /// The hubspot domain, requested over https.
     pub fn domain(mut self, domain: &str) -> Self {
-        self.domain = Some(domain.to_owned());
+        self.base_url = Some(format!("https://{domain}"));
+        self
+    }
+
+    /// The full base url of the api, eg. a local mock server or a proxy path prefix.
+    /// Request paths are joined onto it.
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = Some(base_url.to_owned());
+        self
+    }
+
+    /// The data center hosting the portal's api.
+    pub fn region(mut self, region: Region) -> Self {
+        self.base_url = Some(region.base_url().to_owned());
         self
     }
 
@@ -60,6 +114,13 @@ impl HubspotBuilder {
         self
     }
 
+    /// Authenticate with OAuth instead of a private app token.
+    /// Access tokens are refreshed automatically as they expire.
+    pub fn oauth(mut self, oauth: OAuth) -> Self {
+        self.oauth = Some(oauth);
+        self
+    }
+
     // The hubspot portal_id to validate the request
     pub fn portal_id(mut self, portal_id: &str) -> Self {
         self.portal_id = Some(portal_id.to_owned());
@@ -71,15 +132,36 @@ impl HubspotBuilder {
         self.client = Some(client.to_owned());
         self
     }
+
+    /// The transport to send requests with, instead of the reqwest client.
+    pub fn transport(mut self, transport: Arc<dyn HttpTransport>) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// How failed requests are retried. Requests are not retried unless a policy is set.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Throttle requests to stay within Hubspot's quota.
+    /// Without a rate limit the quota is still tracked, but requests are not throttled.
+    pub fn rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
 }
 
 /// The error type which is returned from building a [Hubspot].
 #[allow(clippy::enum_variant_names)]
 #[derive(Debug, PartialOrd, PartialEq)]
 pub enum HubspotBuilderError {
-    /// Indicates builder didn't set [HubspotBuilder::domain].
+    /// Indicates builder didn't set [HubspotBuilder::domain], [HubspotBuilder::base_url] or [HubspotBuilder::region].
     MissingDomain,
-    /// Indicates builder didn't set [HubspotBuilder::token].
+    /// Indicates [HubspotBuilder::base_url] is not a valid http or https url.
+    InvalidBaseUrl,
+    /// Indicates builder didn't set [HubspotBuilder::token] or [HubspotBuilder::oauth].
     MissingToken,
     /// Indicates builder didn't set [HubspotBuilder::portal_id].
     MissingPortalId,
@@ -92,3 +174,65 @@ impl Display for HubspotBuilderError {
 }
 
 impl Error for HubspotBuilderError {}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::StatusCode;
+
+    use super::*;
+    use crate::client::transport::{HttpResponse, MockTransport};
+
+    async fn archive_url(builder: HubspotBuilder) -> String {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond(HttpResponse::new(StatusCode::NO_CONTENT, Vec::new()));
+        let hubspot = builder
+            .token("token")
+            .portal_id("1")
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+
+        hubspot
+            .objects
+            .contacts
+            .archive("1".to_string())
+            .await
+            .unwrap();
+
+        transport.requests()[0].url.to_string()
+    }
+
+    #[tokio::test]
+    async fn joins_request_paths_onto_the_base_url() {
+        assert_eq!(
+            archive_url(HubspotBuilder::new().base_url("http://localhost:8080/hubspot/")).await,
+            "http://localhost:8080/hubspot/crm/v3/objects/contacts/1"
+        );
+        assert_eq!(
+            archive_url(HubspotBuilder::new().domain("api.hubapi.com")).await,
+            "https://api.hubapi.com/crm/v3/objects/contacts/1"
+        );
+    }
+
+    #[tokio::test]
+    async fn sends_requests_to_the_region() {
+        assert_eq!(
+            archive_url(HubspotBuilder::new().region(Region::Eu1)).await,
+            "https://api-eu1.hubapi.com/crm/v3/objects/contacts/1"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_base_urls() {
+        for base_url in ["api.hubapi.com", "ftp://api.hubapi.com"] {
+            let error = HubspotBuilder::new()
+                .base_url(base_url)
+                .token("token")
+                .portal_id("1")
+                .build()
+                .unwrap_err();
+
+            assert_eq!(error, HubspotBuilderError::InvalidBaseUrl);
+        }
+    }
+}
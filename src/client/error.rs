@@ -1,8 +1,10 @@
 //! Error type for hubspot requests.
 use serde_json::Error as JsonError;
+use std::collections::HashMap;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 
+use reqwest::StatusCode;
 use serde::Deserialize;
 
 /// Hubspot result type
@@ -15,8 +17,112 @@ pub enum HubspotError {
     Json(JsonError),
     /// Generic http error.
     Http(reqwest::Error),
-    /// Hubspot server side error.
+    /// The request could not be authenticated, eg. no OAuth token has been stored.
+    Auth(String),
+    /// An error reported by Hubspot as a message only.
+    ///
+    /// Kept for compatibility, error responses are returned as [HubspotError::Api] or [HubspotError::Response].
     Hubspot(String),
+    /// An error response whose body is not a Hubspot error, eg. a gateway's HTML page or a plain-text 404.
+    Response {
+        /// The HTTP status of the response.
+        status: StatusCode,
+        /// The body of the response.
+        body: String,
+    },
+    /// An error response from Hubspot, with its parsed error body.
+    Api {
+        /// The HTTP status of the response.
+        status: StatusCode,
+        /// The error returned by Hubspot.
+        error: Box<HubspotErrorResponse>,
+    },
+    /// A successful response that didn't contain what the request expected, eg. an upsert
+    /// that returned no record, or a mock transport with no response queued.
+    UnexpectedResponse(String),
+    /// The inputs of a call were rejected before sending, eg. the same record twice in a batch update.
+    InvalidInput(String),
+    /// An association type or label could not be resolved, eg. a label name that doesn't exist.
+    Association(String),
+    /// Some inputs of a batch request failed, with the [BatchErrorPolicy::FailOnError] policy.
+    ///
+    /// [BatchErrorPolicy::FailOnError]: crate::batch::BatchErrorPolicy::FailOnError
+    Batch {
+        /// The errors of the failed inputs.
+        errors: Vec<BatchError>,
+    },
+    /// The request was retried and still failed.
+    Retried {
+        /// The number of attempts made, including the first request.
+        attempts: u32,
+        /// The error of the final attempt.
+        error: Box<HubspotError>,
+    },
+}
+
+impl HubspotError {
+    /// The HTTP status of the response, if Hubspot responded.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            HubspotError::Api { status, .. } | HubspotError::Response { status, .. } => {
+                Some(*status)
+            }
+            HubspotError::Http(err) => err.status(),
+            HubspotError::Retried { error, .. } => error.status(),
+            _ => None,
+        }
+    }
+
+    /// The error returned by Hubspot, if the response could be parsed.
+    pub fn response(&self) -> Option<&HubspotErrorResponse> {
+        match self {
+            HubspotError::Api { error, .. } => Some(error),
+            HubspotError::Retried { error, .. } => error.response(),
+            _ => None,
+        }
+    }
+
+    /// The errors of the failed inputs of a batch request.
+    pub fn batch_errors(&self) -> &[BatchError] {
+        match self {
+            HubspotError::Batch { errors } => errors,
+            HubspotError::Retried { error, .. } => error.batch_errors(),
+            _ => &[],
+        }
+    }
+
+    /// The error category reported by Hubspot, eg. `VALIDATION_ERROR`.
+    pub fn category(&self) -> Option<&str> {
+        self.response().map(|error| error.category.as_str())
+    }
+
+    /// The correlation ID to quote when contacting Hubspot support.
+    pub fn correlation_id(&self) -> Option<&str> {
+        self.response()
+            .and_then(|error| error.correlation_id.as_deref())
+    }
+
+    /// Whether the requested record or resource does not exist.
+    pub fn is_not_found(&self) -> bool {
+        self.status() == Some(StatusCode::NOT_FOUND) || self.category() == Some("OBJECT_NOT_FOUND")
+    }
+
+    /// Whether the request was rejected by Hubspot's rate limits.
+    pub fn is_rate_limited(&self) -> bool {
+        self.status() == Some(StatusCode::TOO_MANY_REQUESTS)
+            || self.category() == Some("RATE_LIMITS")
+    }
+
+    /// Whether the request conflicts with an existing record, eg. a duplicate unique property.
+    pub fn is_conflict(&self) -> bool {
+        self.status() == Some(StatusCode::CONFLICT) || self.category() == Some("CONFLICT")
+    }
+
+    /// Whether the request was rejected because of invalid input.
+    pub fn is_validation(&self) -> bool {
+        self.status() == Some(StatusCode::BAD_REQUEST)
+            || self.category() == Some("VALIDATION_ERROR")
+    }
 }
 
 impl Display for HubspotError {
@@ -40,23 +146,212 @@ impl From<reqwest::Error> for HubspotError {
 }
 
 /// Hubspot error response.
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct HubspotErrorResponse {
+    /// A description of the error.
+    pub message: String,
+    /// The category of the error, eg. `VALIDATION_ERROR` or `OBJECT_NOT_FOUND`.
+    #[serde(default)]
+    pub category: String,
+    /// A more specific category of the error.
+    pub sub_category: Option<String>,
+    /// A unique ID for the request, to quote when contacting Hubspot support.
+    pub correlation_id: Option<String>,
+    /// The individual errors, eg. one per invalid property.
+    #[serde(default)]
+    pub errors: Vec<HubspotErrorDetail>,
+    /// Context about the error condition, eg. the invalid properties.
+    #[serde(default)]
+    pub context: HashMap<String, Vec<String>>,
+    /// Links to documentation about the error.
+    #[serde(default)]
+    pub links: HashMap<String, String>,
+}
+
+/// A single error within a Hubspot error response.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HubspotErrorDetail {
+    /// A description of the error.
     pub message: String,
-    pub context: HubspotErrorContext,
+    /// The name of the field or property in error.
+    #[serde(rename = "in")]
+    pub field: Option<String>,
+    /// The status code associated with the error detail, eg. `INVALID_EMAIL`.
+    pub code: Option<String>,
+    /// A more specific category of the error.
+    pub sub_category: Option<String>,
+    /// Context about the error condition.
+    #[serde(default)]
+    pub context: HashMap<String, Vec<String>>,
+}
+
+/// The error of one or more inputs of a batch request.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchError {
+    /// The status of the error, usually `error`.
+    #[serde(default)]
+    pub status: String,
+    /// A description of the error.
+    #[serde(default)]
+    pub message: String,
+    /// The category of the error, eg. `VALIDATION_ERROR` or `OBJECT_NOT_FOUND`.
+    #[serde(default)]
     pub category: String,
+    /// A more specific category of the error.
+    pub sub_category: Option<String>,
+    /// The individual errors, eg. one per invalid property.
+    #[serde(default)]
+    pub errors: Vec<HubspotErrorDetail>,
+    /// Context about the error condition, including the `ids` of the failed inputs.
+    #[serde(default)]
+    pub context: HashMap<String, Vec<String>>,
+    /// Links to documentation about the error.
+    #[serde(default)]
+    pub links: HashMap<String, String>,
+    /// The HTTP status of the request, when the request of the inputs failed as a whole.
+    #[serde(skip)]
+    pub request_status: Option<StatusCode>,
+    /// The positions of the inputs in the batch call, when their request failed as a whole.
+    /// Identifies failed inputs without an ID, such as records to create.
+    #[serde(skip)]
+    pub input_indexes: Vec<usize>,
 }
 
-#[derive(Deserialize, Debug)]
-pub struct HubspotErrorContext {
-    pub properties: Vec<String>,
+impl BatchError {
+    /// The IDs of the inputs the error applies to, as given in the request.
+    pub fn ids(&self) -> &[String] {
+        self.context.get("ids").map_or(&[], Vec::as_slice)
+    }
+
+    /// The error of a batch request that failed as a whole, applying to each of its inputs.
+    pub(crate) fn from_failed_request(
+        error: &HubspotError,
+        ids: Vec<String>,
+        input_indexes: Vec<usize>,
+    ) -> Self {
+        let response = error.response();
+        let mut context = response
+            .map(|response| response.context.clone())
+            .unwrap_or_default();
+        if !ids.is_empty() {
+            context.insert("ids".to_owned(), ids);
+        }
+
+        Self {
+            status: "error".to_owned(),
+            message: response
+                .map_or_else(|| error.to_string(), |response| response.message.clone()),
+            category: error.category().unwrap_or_default().to_owned(),
+            sub_category: response.and_then(|response| response.sub_category.clone()),
+            errors: response
+                .map(|response| response.errors.clone())
+                .unwrap_or_default(),
+            context,
+            links: response
+                .map(|response| response.links.clone())
+                .unwrap_or_default(),
+            request_status: error.status(),
+            input_indexes,
+        }
+    }
+
+    /// Whether the inputs may succeed if sent again, being rate limited or failed by a server error.
+    pub fn is_transient(&self) -> bool {
+        self.category == "RATE_LIMITS"
+            || self.request_status.is_some_and(|status| {
+                status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+            })
+    }
 }
 
-impl From<HubspotErrorResponse> for HubspotError {
-    fn from(inner: HubspotErrorResponse) -> Self {
-        HubspotError::Hubspot(format!(
-            "{}: {}, {:?}",
-            inner.category, inner.message, inner.context
-        ))
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::client::transport::HttpResponse;
+    use crate::client::HubspotClient;
+
+    fn read(status: StatusCode, body: &str) -> HubspotError {
+        HubspotClient::read_response::<Value>(HttpResponse::new(status, body.as_bytes().to_vec()))
+            .unwrap_err()
+    }
+
+    #[test]
+    fn parses_hubspot_error_bodies() {
+        let body = json!({
+            "status": "error",
+            "message": "Property values were not valid",
+            "correlationId": "aeb5f871-7f07-4993-9211-075dc63e7cbf",
+            "category": "VALIDATION_ERROR",
+            "errors": [{ "message": "Invalid email", "in": "email", "code": "INVALID_EMAIL" }],
+            "context": { "propertyName": ["email"] },
+        });
+        let error = read(StatusCode::BAD_REQUEST, &body.to_string());
+
+        assert!(matches!(error, HubspotError::Api { .. }));
+        assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST));
+        assert_eq!(error.category(), Some("VALIDATION_ERROR"));
+        assert_eq!(
+            error.correlation_id(),
+            Some("aeb5f871-7f07-4993-9211-075dc63e7cbf")
+        );
+        let response = error.response().unwrap();
+        assert_eq!(response.errors[0].field.as_deref(), Some("email"));
+        assert_eq!(response.context["propertyName"], vec!["email"]);
+    }
+
+    #[test]
+    fn keeps_bodies_that_are_not_hubspot_errors() {
+        let error = read(StatusCode::BAD_GATEWAY, "<html>Bad Gateway</html>");
+
+        match &error {
+            HubspotError::Response { status, body } => {
+                assert_eq!(*status, StatusCode::BAD_GATEWAY);
+                assert_eq!(body, "<html>Bad Gateway</html>");
+            }
+            error => panic!("Unexpected error {error}"),
+        }
+        assert!(error.response().is_none());
+    }
+
+    #[test]
+    fn classifies_errors_by_status_and_category() {
+        let not_found = read(StatusCode::NOT_FOUND, "Not Found");
+        let rate_limited = read(
+            StatusCode::TOO_MANY_REQUESTS,
+            r#"{"message": "Too many requests", "category": "RATE_LIMITS"}"#,
+        );
+        let conflict = read(
+            StatusCode::CONFLICT,
+            r#"{"message": "Contact already exists", "category": "CONFLICT"}"#,
+        );
+        let validation = read(
+            StatusCode::BAD_REQUEST,
+            r#"{"message": "Invalid input", "category": "VALIDATION_ERROR"}"#,
+        );
+
+        assert!(not_found.is_not_found() && !not_found.is_validation());
+        assert!(rate_limited.is_rate_limited() && !rate_limited.is_conflict());
+        assert!(conflict.is_conflict() && !conflict.is_not_found());
+        assert!(validation.is_validation() && !validation.is_rate_limited());
+    }
+
+    #[test]
+    fn looks_through_retried_errors() {
+        let error = HubspotError::Retried {
+            attempts: 3,
+            error: Box::new(read(
+                StatusCode::TOO_MANY_REQUESTS,
+                r#"{"message": "Too many requests", "category": "RATE_LIMITS"}"#,
+            )),
+        };
+
+        assert_eq!(error.status(), Some(StatusCode::TOO_MANY_REQUESTS));
+        assert_eq!(error.category(), Some("RATE_LIMITS"));
+        assert!(error.is_rate_limited());
     }
 }
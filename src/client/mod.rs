@@ -1,59 +1,186 @@
-use reqwest::{Client, Method, RequestBuilder};
+use std::sync::Arc;
+
+use reqwest::{
+    header::{HeaderValue, AUTHORIZATION},
+    Client, Method, RequestBuilder, StatusCode,
+};
 use serde::de::DeserializeOwned;
 
 use self::error::{HubspotError, HubspotErrorResponse, HubspotResult};
+use self::oauth::OAuth;
+use self::rate_limit::{RateLimit, RateLimitSnapshot, RateLimiter};
+use self::retry::{is_idempotent, RetryPolicy};
+use self::transport::{HttpRequest, HttpResponse, HttpTransport, ReqwestTransport};
 
 pub mod error;
+pub mod oauth;
+pub mod rate_limit;
+pub mod retry;
+pub mod transport;
+
+/// How requests are authenticated.
+#[derive(Debug)]
+pub enum Auth {
+    /// Your private app access token
+    Token(String),
+    /// OAuth access tokens of a public app, refreshed as they expire.
+    OAuth(OAuth),
+}
+
+impl Auth {
+    /// The access token to send with the next request.
+    async fn access_token(&self) -> HubspotResult<String> {
+        match self {
+            Auth::Token(token) => Ok(token.clone()),
+            Auth::OAuth(oauth) => oauth.access_token().await,
+        }
+    }
+}
 
 #[derive(Debug)]
 pub struct HubspotClient {
+    /// Builds the requests handed to the transport.
     client: Client,
-    /// Your private app access token
-    token: String,
-    domain: String,
+    /// Sends requests to Hubspot.
+    transport: Arc<dyn HttpTransport>,
+    /// How requests are authenticated.
+    auth: Auth,
+    /// The base url request paths are joined onto.
+    base_url: String,
     /// The portal ID of your Hubspot account.
     /// Can be used to validate requests.
     pub portal_id: String,
+    /// How failed requests are retried.
+    retry_policy: RetryPolicy,
+    /// Throttles requests to stay within Hubspot's quota.
+    rate_limiter: RateLimiter,
 }
 
 impl HubspotClient {
     /// Create HubspotClient
-    pub fn new(client: Client, domain: &str, token: &str, portal_id: &str) -> Self {
+    pub fn new(client: Client, base_url: &str, auth: Auth, portal_id: &str) -> Self {
         Self {
+            transport: Arc::new(ReqwestTransport::new(client.clone())),
             client,
-            domain: domain.to_owned(),
-            token: token.to_owned(),
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            auth,
             portal_id: portal_id.to_owned(),
+            retry_policy: RetryPolicy::none(),
+            rate_limiter: RateLimiter::new(RateLimit::disabled()),
         }
     }
 
+    /// Set the transport used to send requests.
+    pub fn with_transport(mut self, transport: Arc<dyn HttpTransport>) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// Set the retry policy used when sending requests.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Set the rate limit used to throttle outgoing requests.
+    pub fn with_rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limiter = RateLimiter::new(rate_limit);
+        self
+    }
+
+    /// The most recent quota reported by Hubspot.
+    pub fn rate_limit(&self) -> RateLimitSnapshot {
+        self.rate_limiter.snapshot()
+    }
+
     /// Send a hubspot request
     pub async fn send<R>(&self, req: RequestBuilder) -> HubspotResult<R>
     where
         R: DeserializeOwned,
     {
-        let res = req.bearer_auth(&self.token).send().await?;
+        let request = HttpRequest::from_request(req.build()?);
+        let idempotent = is_idempotent(&request);
+        let search = is_search(&request);
+
+        let mut attempts = 1;
+        let mut refreshed = false;
+        let outcome = loop {
+            let token = self.auth.access_token().await?;
 
-        if res.status().is_success() {
-            let body = res.bytes().await?;
-            let body = body.to_vec();
-            let body = std::str::from_utf8(&body).unwrap();
+            let mut attempt = request.clone();
+            authorize(&mut attempt, &token)?;
+            let outcome = self.execute(attempt, search).await;
 
+            // A rejected OAuth token is refreshed and the request sent again, once.
+            // The resend is not a retry, so it doesn't count against the retry policy.
+            if let (Auth::OAuth(oauth), Ok(res)) = (&self.auth, &outcome) {
+                if res.status == StatusCode::UNAUTHORIZED && !refreshed {
+                    refreshed = true;
+                    oauth.refresh_rejected(&token).await?;
+                    continue;
+                }
+            }
+
+            match self.retry_policy.delay_for(&outcome, attempts, idempotent) {
+                Some(delay) => {
+                    tokio::time::sleep(delay).await;
+                    attempts += 1;
+                }
+                None => break outcome,
+            }
+        };
+
+        let result = outcome.and_then(Self::read_response);
+
+        match result {
+            Err(error) if attempts > 1 => Err(HubspotError::Retried {
+                attempts,
+                error: Box::new(error),
+            }),
+            result => result,
+        }
+    }
+
+    /// Execute a single attempt of a request, within the rate limit.
+    async fn execute(&self, request: HttpRequest, search: bool) -> HubspotResult<HttpResponse> {
+        self.rate_limiter.acquire(search).await;
+
+        let res = self.transport.send(request).await?;
+        self.rate_limiter.observe(
+            &res.headers,
+            search,
+            res.status == StatusCode::TOO_MANY_REQUESTS,
+        );
+
+        Ok(res)
+    }
+
+    /// Deserialize a hubspot response into either the result or the error it represents.
+    pub(crate) fn read_response<R>(res: HttpResponse) -> HubspotResult<R>
+    where
+        R: DeserializeOwned,
+    {
+        let status = res.status;
+        let body = String::from_utf8_lossy(&res.body);
+
+        if status.is_success() {
             if body.is_empty() {
                 Ok(serde_json::from_str::<R>("null")?)
             } else {
-                Ok(serde_json::from_str::<R>(body)?)
+                Ok(serde_json::from_str::<R>(&body)?)
             }
         } else {
-            let body = res.bytes().await?;
-            let body = body.to_vec();
-            let body = std::str::from_utf8(&body).unwrap();
-
-            let err = serde_json::from_str::<HubspotErrorResponse>(body);
-            if let Ok(err) = err {
-                Err(HubspotError::from(err))
+            let err = serde_json::from_str::<HubspotErrorResponse>(&body);
+            if let Ok(error) = err {
+                Err(HubspotError::Api {
+                    status,
+                    error: Box::new(error),
+                })
             } else {
-                Err(HubspotError::Hubspot(body.to_owned()))
+                Err(HubspotError::Response {
+                    status,
+                    body: body.into_owned(),
+                })
             }
         }
     }
@@ -63,7 +190,162 @@ impl HubspotClient {
     /// * `method` = The HTTP request method.
     /// * `path` - The HTTP request path.
     pub fn begin(&self, method: Method, path: &str) -> RequestBuilder {
-        self.client
-            .request(method, format!("https://{}/{}", self.domain, path))
+        self.client.request(
+            method,
+            format!("{}/{}", self.base_url, path.trim_start_matches('/')),
+        )
+    }
+}
+
+/// Set the bearer token of a request.
+fn authorize(request: &mut HttpRequest, token: &str) -> HubspotResult<()> {
+    let mut value = HeaderValue::from_str(&format!("Bearer {token}"))
+        .map_err(|_| HubspotError::Auth("The access token is not a valid header".to_string()))?;
+    value.set_sensitive(true);
+    request.headers.insert(AUTHORIZATION, value);
+
+    Ok(())
+}
+
+/// Search endpoints have their own, stricter rate limit.
+fn is_search(req: &HttpRequest) -> bool {
+    req.method == Method::POST && req.url.path().ends_with("/search")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use reqwest::header::RETRY_AFTER;
+    use serde_json::{json, Value};
+    use time::OffsetDateTime;
+
+    use super::oauth::{MemoryTokenStore, OAuthConfig, OAuthToken};
+    use super::transport::MockTransport;
+    use super::*;
+
+    fn client(transport: &Arc<MockTransport>, auth: Auth) -> HubspotClient {
+        HubspotClient::new(Client::new(), "https://api.hubapi.com", auth, "1")
+            .with_transport(transport.clone())
+            .with_retry_policy(
+                RetryPolicy::new()
+                    .base_delay(Duration::from_millis(1))
+                    .jitter(false),
+            )
+    }
+
+    fn oauth(transport: &Arc<MockTransport>) -> Auth {
+        let token = OAuthToken {
+            access_token: "rejected".to_string(),
+            refresh_token: "refresh".to_string(),
+            expires_at: OffsetDateTime::now_utc() + time::Duration::hours(1),
+        };
+        let store = Arc::new(MemoryTokenStore::new(token));
+
+        Auth::OAuth(
+            OAuth::new(
+                OAuthConfig::new("id", "secret", "https://example.com"),
+                store,
+            )
+            .transport(transport.clone()),
+        )
+    }
+
+    fn token_response() -> Value {
+        json!({ "access_token": "refreshed", "refresh_token": "refresh", "expires_in": 1800 })
+    }
+
+    async fn get(client: &HubspotClient) -> HubspotResult<Value> {
+        client
+            .send(client.begin(Method::GET, "crm/v3/objects/contacts/1"))
+            .await
+    }
+
+    #[tokio::test]
+    async fn retries_rate_limited_requests() {
+        let transport = Arc::new(MockTransport::new());
+        let mut limited = HttpResponse::new(StatusCode::TOO_MANY_REQUESTS, Vec::new());
+        limited
+            .headers
+            .insert(RETRY_AFTER, HeaderValue::from_static("0"));
+        transport
+            .respond(limited)
+            .respond_json(StatusCode::OK, &json!({ "id": "1" }));
+
+        let record = get(&client(&transport, Auth::Token("token".to_string())))
+            .await
+            .unwrap();
+
+        assert_eq!(record, json!({ "id": "1" }));
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn reports_the_attempts_once_retries_are_exhausted() {
+        let transport = Arc::new(MockTransport::new());
+        for _ in 0..3 {
+            transport.respond_json(StatusCode::SERVICE_UNAVAILABLE, &json!({}));
+        }
+
+        let error = get(&client(&transport, Auth::Token("token".to_string())))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, HubspotError::Retried { attempts: 3, .. }));
+        assert_eq!(error.status(), Some(StatusCode::SERVICE_UNAVAILABLE));
+        assert_eq!(transport.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_server_errors_of_writes() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond_json(StatusCode::INTERNAL_SERVER_ERROR, &json!({}));
+
+        let client = client(&transport, Auth::Token("token".to_string()));
+        let error = client
+            .send::<Value>(client.begin(Method::POST, "crm/v3/objects/contacts"))
+            .await
+            .unwrap_err();
+
+        assert!(!matches!(error, HubspotError::Retried { .. }));
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn resending_a_rejected_token_is_not_a_retry() {
+        let transport = Arc::new(MockTransport::new());
+        transport
+            .respond_json(StatusCode::UNAUTHORIZED, &json!({}))
+            .respond_json(StatusCode::OK, &token_response())
+            .respond_json(StatusCode::TOO_MANY_REQUESTS, &json!({}))
+            .respond_json(StatusCode::OK, &json!({}));
+
+        let client = client(&transport, oauth(&transport)).with_retry_policy(
+            RetryPolicy::new()
+                .max_attempts(2)
+                .base_delay(Duration::from_millis(1)),
+        );
+
+        assert!(get(&client).await.is_ok());
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 4);
+        assert_eq!(requests[0].headers[AUTHORIZATION], "Bearer rejected");
+        assert_eq!(requests[3].headers[AUTHORIZATION], "Bearer refreshed");
+    }
+
+    #[tokio::test]
+    async fn does_not_report_a_resent_request_as_retried() {
+        let transport = Arc::new(MockTransport::new());
+        transport
+            .respond_json(StatusCode::UNAUTHORIZED, &json!({}))
+            .respond_json(StatusCode::OK, &token_response())
+            .respond_json(StatusCode::BAD_REQUEST, &json!({}));
+
+        let error = get(&client(&transport, oauth(&transport)))
+            .await
+            .unwrap_err();
+
+        assert!(!matches!(error, HubspotError::Retried { .. }));
+        assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST));
     }
 }
@@ -0,0 +1,450 @@
+//! OAuth 2.0 authentication for public apps installed into many Hubspot portals.
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use reqwest::{Client, Url};
+use serde::{Deserialize, Serialize};
+use time::{Duration, OffsetDateTime};
+
+use super::error::{HubspotError, HubspotResult};
+use super::transport::{HttpRequest, HttpTransport, ReqwestTransport};
+use super::HubspotClient;
+
+const AUTHORIZE_URL: &str = "https://app.hubspot.com/oauth/authorize";
+const DEFAULT_BASE_URL: &str = "https://api.hubapi.com";
+
+/// Access tokens are refreshed this long before they expire, to allow for clock skew and latency.
+const EXPIRY_MARGIN: Duration = Duration::seconds(60);
+
+/// An OAuth access token and the refresh token used to renew it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OAuthToken {
+    /// The access token sent with each request.
+    pub access_token: String,
+    /// The refresh token used to obtain a new access token.
+    pub refresh_token: String,
+    /// When the access token expires.
+    #[serde(with = "time::serde::rfc3339")]
+    pub expires_at: OffsetDateTime,
+}
+
+impl OAuthToken {
+    /// Whether the access token has expired, or is about to.
+    pub fn is_expired(&self) -> bool {
+        self.expires_at - EXPIRY_MARGIN <= OffsetDateTime::now_utc()
+    }
+}
+
+/// The token response returned by Hubspot's token endpoint.
+#[derive(Deserialize, Debug)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: String,
+    /// The number of seconds until the access token expires.
+    expires_in: i64,
+}
+
+impl From<TokenResponse> for OAuthToken {
+    fn from(res: TokenResponse) -> Self {
+        Self {
+            access_token: res.access_token,
+            refresh_token: res.refresh_token,
+            expires_at: OffsetDateTime::now_utc() + Duration::seconds(res.expires_in),
+        }
+    }
+}
+
+/// Storage for the OAuth tokens of a portal.
+///
+/// Implement this trait to persist tokens across restarts, eg. in a database keyed by portal ID.
+#[async_trait]
+pub trait TokenStore: Debug + Send + Sync {
+    /// Load the current token, if one has been stored.
+    async fn load(&self) -> HubspotResult<Option<OAuthToken>>;
+
+    /// Store a newly issued token.
+    async fn store(&self, token: &OAuthToken) -> HubspotResult<()>;
+}
+
+/// A token store that keeps the token in memory.
+#[derive(Debug, Default)]
+pub struct MemoryTokenStore {
+    token: Mutex<Option<OAuthToken>>,
+}
+
+impl MemoryTokenStore {
+    /// Create a token store holding the given token.
+    pub fn new(token: OAuthToken) -> Self {
+        Self {
+            token: Mutex::new(Some(token)),
+        }
+    }
+}
+
+#[async_trait]
+impl TokenStore for MemoryTokenStore {
+    async fn load(&self) -> HubspotResult<Option<OAuthToken>> {
+        Ok(self.token.lock().unwrap().clone())
+    }
+
+    async fn store(&self, token: &OAuthToken) -> HubspotResult<()> {
+        *self.token.lock().unwrap() = Some(token.clone());
+        Ok(())
+    }
+}
+
+/// The OAuth credentials of a Hubspot public app.
+#[derive(Clone, Debug)]
+pub struct OAuthConfig {
+    client_id: String,
+    client_secret: String,
+    redirect_uri: String,
+}
+
+impl OAuthConfig {
+    /// Create the OAuth configuration of an app.
+    pub fn new(client_id: &str, client_secret: &str, redirect_uri: &str) -> Self {
+        Self {
+            client_id: client_id.to_owned(),
+            client_secret: client_secret.to_owned(),
+            redirect_uri: redirect_uri.to_owned(),
+        }
+    }
+}
+
+/// Builds the url users visit to install the app into their portal.
+#[derive(Clone, Debug)]
+pub struct AuthorizeUrl {
+    client_id: String,
+    redirect_uri: String,
+    scopes: Vec<String>,
+    optional_scopes: Vec<String>,
+    state: Option<String>,
+}
+
+impl AuthorizeUrl {
+    /// A scope the app requires.
+    pub fn scope(mut self, scope: &str) -> Self {
+        self.scopes.push(scope.to_owned());
+        self
+    }
+
+    /// A scope the app can use if the portal has access to it.
+    pub fn optional_scope(mut self, scope: &str) -> Self {
+        self.optional_scopes.push(scope.to_owned());
+        self
+    }
+
+    /// Override the redirect uri of the app configuration.
+    pub fn redirect_uri(mut self, redirect_uri: &str) -> Self {
+        self.redirect_uri = redirect_uri.to_owned();
+        self
+    }
+
+    /// A value passed back to the redirect uri, to protect against cross-site request forgery.
+    pub fn state(mut self, state: &str) -> Self {
+        self.state = Some(state.to_owned());
+        self
+    }
+
+    /// Build the authorize url.
+    pub fn build(&self) -> String {
+        let mut params = vec![
+            ("client_id", self.client_id.clone()),
+            ("redirect_uri", self.redirect_uri.clone()),
+            ("scope", self.scopes.join(" ")),
+        ];
+        if !self.optional_scopes.is_empty() {
+            params.push(("optional_scope", self.optional_scopes.join(" ")));
+        }
+        if let Some(state) = &self.state {
+            params.push(("state", state.clone()));
+        }
+
+        Url::parse_with_params(AUTHORIZE_URL, &params)
+            .expect("The authorize url is valid")
+            .to_string()
+    }
+}
+
+/// OAuth authentication for a single portal.
+///
+/// Access tokens are loaded from the [TokenStore] and refreshed when they expire,
+/// or when Hubspot rejects them.
+#[derive(Clone, Debug)]
+pub struct OAuth {
+    config: OAuthConfig,
+    store: Arc<dyn TokenStore>,
+    /// Builds the token requests handed to the transport.
+    client: Client,
+    /// The base url of the api the token endpoint is requested from.
+    base_url: Option<String>,
+    /// Sends token requests to Hubspot, or the reqwest client if not set.
+    transport: Option<Arc<dyn HttpTransport>>,
+    /// Serialises token refreshes, so a refresh token is only used once.
+    refresh_lock: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl OAuth {
+    /// Create OAuth authentication using the app configuration and token store.
+    pub fn new(config: OAuthConfig, store: Arc<dyn TokenStore>) -> Self {
+        Self {
+            config,
+            store,
+            client: Client::new(),
+            base_url: None,
+            transport: None,
+            refresh_lock: Arc::new(tokio::sync::Mutex::new(())),
+        }
+    }
+
+    /// The reqwest client used to request tokens.
+    pub fn client(mut self, client: &Client) -> Self {
+        self.client = client.to_owned();
+        self
+    }
+
+    /// The base url of the api tokens are requested from, eg. a local mock server.
+    ///
+    /// When passed to the builder of [Hubspot] without a base url, the builder's is used.
+    ///
+    /// [Hubspot]: crate::Hubspot
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = Some(base_url.to_owned());
+        self
+    }
+
+    /// The transport used to request tokens.
+    ///
+    /// When passed to the builder of [Hubspot] without a transport, the builder's is used.
+    ///
+    /// [Hubspot]: crate::Hubspot
+    pub fn transport(mut self, transport: Arc<dyn HttpTransport>) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Use the base url and transport of the Hubspot client, unless set on the OAuth.
+    pub(crate) fn with_defaults(
+        mut self,
+        base_url: &str,
+        transport: Option<&Arc<dyn HttpTransport>>,
+    ) -> Self {
+        self.base_url.get_or_insert_with(|| base_url.to_owned());
+        if self.transport.is_none() {
+            self.transport = transport.cloned();
+        }
+        self
+    }
+
+    /// Start building the url users visit to install the app.
+    pub fn authorize_url(&self) -> AuthorizeUrl {
+        AuthorizeUrl {
+            client_id: self.config.client_id.clone(),
+            redirect_uri: self.config.redirect_uri.clone(),
+            scopes: Vec::new(),
+            optional_scopes: Vec::new(),
+            state: None,
+        }
+    }
+
+    /// Exchange the code passed to the redirect uri for a token, and store it.
+    pub async fn exchange_code(&self, code: &str) -> HubspotResult<OAuthToken> {
+        self.request_token(&[
+            ("grant_type", "authorization_code"),
+            ("client_id", &self.config.client_id),
+            ("client_secret", &self.config.client_secret),
+            ("redirect_uri", &self.config.redirect_uri),
+            ("code", code),
+        ])
+        .await
+    }
+
+    /// Refresh the access token using the stored refresh token, and store the new token.
+    pub async fn refresh(&self) -> HubspotResult<OAuthToken> {
+        let _guard = self.refresh_lock.lock().await;
+        let token = self.load().await?;
+
+        self.refresh_token(&token).await
+    }
+
+    /// The current access token, refreshed if it has expired.
+    pub(crate) async fn access_token(&self) -> HubspotResult<String> {
+        let token = self.load().await?;
+        if !token.is_expired() {
+            return Ok(token.access_token);
+        }
+
+        let _guard = self.refresh_lock.lock().await;
+        let token = self.load().await?;
+        if token.is_expired() {
+            Ok(self.refresh_token(&token).await?.access_token)
+        } else {
+            Ok(token.access_token)
+        }
+    }
+
+    /// Refresh an access token rejected by Hubspot, unless another request already refreshed it.
+    pub(crate) async fn refresh_rejected(&self, rejected: &str) -> HubspotResult<()> {
+        let _guard = self.refresh_lock.lock().await;
+        let token = self.load().await?;
+        if token.access_token == rejected {
+            self.refresh_token(&token).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn load(&self) -> HubspotResult<OAuthToken> {
+        self.store.load().await?.ok_or_else(|| {
+            HubspotError::Auth("No OAuth token has been stored for the portal".to_string())
+        })
+    }
+
+    async fn refresh_token(&self, token: &OAuthToken) -> HubspotResult<OAuthToken> {
+        self.request_token(&[
+            ("grant_type", "refresh_token"),
+            ("client_id", &self.config.client_id),
+            ("client_secret", &self.config.client_secret),
+            ("redirect_uri", &self.config.redirect_uri),
+            ("refresh_token", &token.refresh_token),
+        ])
+        .await
+    }
+
+    async fn request_token(&self, form: &[(&str, &str)]) -> HubspotResult<OAuthToken> {
+        let url = format!(
+            "{}/oauth/v1/token",
+            self.base_url
+                .as_deref()
+                .unwrap_or(DEFAULT_BASE_URL)
+                .trim_end_matches('/')
+        );
+        let req = HttpRequest::from_request(self.client.post(url).form(form).build()?);
+        let res = match &self.transport {
+            Some(transport) => transport.send(req).await?,
+            None => ReqwestTransport::new(self.client.clone()).send(req).await?,
+        };
+        let token = OAuthToken::from(HubspotClient::read_response::<TokenResponse>(res)?);
+        self.store.store(&token).await?;
+
+        Ok(token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::StatusCode;
+    use serde_json::json;
+
+    use super::*;
+    use crate::client::transport::MockTransport;
+
+    fn token(access_token: &str, expires_in: Duration) -> OAuthToken {
+        OAuthToken {
+            access_token: access_token.to_owned(),
+            refresh_token: "refresh".to_owned(),
+            expires_at: OffsetDateTime::now_utc() + expires_in,
+        }
+    }
+
+    fn oauth(transport: &Arc<MockTransport>, token: OAuthToken) -> (OAuth, Arc<MemoryTokenStore>) {
+        let store = Arc::new(MemoryTokenStore::new(token));
+        let oauth = OAuth::new(
+            OAuthConfig::new("id", "secret", "https://example.com/callback"),
+            store.clone(),
+        )
+        .with_defaults(
+            "http://localhost:8080/",
+            Some(&(transport.clone() as Arc<dyn HttpTransport>)),
+        );
+
+        (oauth, store)
+    }
+
+    fn respond_token(transport: &MockTransport) {
+        transport.respond_json(
+            StatusCode::OK,
+            &json!({ "access_token": "refreshed", "refresh_token": "refresh", "expires_in": 1800 }),
+        );
+    }
+
+    #[tokio::test]
+    async fn refreshes_expired_tokens() {
+        let transport = Arc::new(MockTransport::new());
+        respond_token(&transport);
+        let (oauth, store) = oauth(&transport, token("expired", Duration::seconds(30)));
+
+        assert_eq!(oauth.access_token().await.unwrap(), "refreshed");
+        assert_eq!(
+            store.load().await.unwrap().unwrap().access_token,
+            "refreshed"
+        );
+
+        let request = &transport.requests()[0];
+        assert_eq!(request.url.as_str(), "http://localhost:8080/oauth/v1/token");
+        let form = String::from_utf8(request.body.clone().unwrap()).unwrap();
+        assert!(form.contains("grant_type=refresh_token"));
+        assert!(form.contains("refresh_token=refresh"));
+    }
+
+    #[tokio::test]
+    async fn uses_valid_tokens_without_refreshing() {
+        let transport = Arc::new(MockTransport::new());
+        let (oauth, _) = oauth(&transport, token("valid", Duration::hours(1)));
+
+        assert_eq!(oauth.access_token().await.unwrap(), "valid");
+        assert!(transport.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn refreshes_a_rejected_token_once() {
+        let transport = Arc::new(MockTransport::new());
+        respond_token(&transport);
+        let (oauth, _) = oauth(&transport, token("rejected", Duration::hours(1)));
+
+        oauth.refresh_rejected("rejected").await.unwrap();
+        // A request that was sent with the old token doesn't refresh the new one.
+        oauth.refresh_rejected("rejected").await.unwrap();
+
+        assert_eq!(oauth.access_token().await.unwrap(), "refreshed");
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[test]
+    fn keeps_its_own_base_url_and_transport() {
+        let transport: Arc<dyn HttpTransport> = Arc::new(MockTransport::new());
+        let oauth = OAuth::new(
+            OAuthConfig::new("id", "secret", "https://example.com/callback"),
+            Arc::new(MemoryTokenStore::default()),
+        )
+        .base_url("http://localhost:9090")
+        .with_defaults("http://localhost:8080", Some(&transport));
+
+        assert_eq!(oauth.base_url.as_deref(), Some("http://localhost:9090"));
+        assert!(oauth.transport.is_some());
+    }
+
+    #[test]
+    fn builds_the_authorize_url() {
+        let oauth = OAuth::new(
+            OAuthConfig::new("id", "secret", "https://example.com/callback"),
+            Arc::new(MemoryTokenStore::default()),
+        );
+
+        let url = oauth
+            .authorize_url()
+            .scope("crm.objects.contacts.read")
+            .scope("oauth")
+            .state("xyz")
+            .build();
+
+        assert_eq!(
+            url,
+            "https://app.hubspot.com/oauth/authorize?client_id=id\
+             &redirect_uri=https%3A%2F%2Fexample.com%2Fcallback\
+             &scope=crm.objects.contacts.read+oauth&state=xyz"
+        );
+    }
+}
@@ -0,0 +1,325 @@
+//! Client side rate limiting driven by Hubspot's rate limit headers.
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use reqwest::header::HeaderMap;
+
+const HEADER_MAX: &str = "x-hubspot-ratelimit-max";
+const HEADER_REMAINING: &str = "x-hubspot-ratelimit-remaining";
+const HEADER_INTERVAL: &str = "x-hubspot-ratelimit-interval-milliseconds";
+const HEADER_SECONDLY: &str = "x-hubspot-ratelimit-secondly";
+const HEADER_SECONDLY_REMAINING: &str = "x-hubspot-ratelimit-secondly-remaining";
+const HEADER_DAILY: &str = "x-hubspot-ratelimit-daily";
+const HEADER_DAILY_REMAINING: &str = "x-hubspot-ratelimit-daily-remaining";
+
+/// Configuration of the client side rate limiter.
+///
+/// The general bucket starts from the configured quota and is resized to the quota Hubspot
+/// reports in its `X-HubSpot-RateLimit-*` headers. Search endpoints do not report their quota,
+/// so they are throttled by their own, stricter bucket.
+///
+/// The daily quota is advisory: it is reported in the [RateLimitSnapshot], but requests are
+/// not held back when it runs out. Check `daily_remaining` to pause work before it does.
+#[derive(Clone, Debug)]
+pub struct RateLimit {
+    throttle: bool,
+    requests: u32,
+    interval: Duration,
+    search_requests: u32,
+    search_interval: Duration,
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        Self {
+            throttle: true,
+            requests: 100,
+            interval: Duration::from_secs(10),
+            search_requests: 5,
+            search_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+impl RateLimit {
+    /// Create a rate limit with Hubspot's default quotas.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Track Hubspot's reported quota without throttling requests.
+    pub fn disabled() -> Self {
+        Self {
+            throttle: false,
+            ..Default::default()
+        }
+    }
+
+    /// The number of requests allowed per interval until Hubspot reports its own quota.
+    pub fn requests(mut self, requests: u32, interval: Duration) -> Self {
+        self.requests = requests.max(1);
+        self.interval = interval;
+        self
+    }
+
+    /// The number of search requests allowed per interval.
+    pub fn search_requests(mut self, requests: u32, interval: Duration) -> Self {
+        self.search_requests = requests.max(1);
+        self.search_interval = interval;
+        self
+    }
+}
+
+/// The most recent quota reported by Hubspot.
+#[derive(Clone, Debug, Default)]
+pub struct RateLimitSnapshot {
+    /// The number of requests allowed per interval.
+    pub interval_max: Option<u32>,
+    /// The number of requests remaining in the current interval.
+    pub interval_remaining: Option<u32>,
+    /// The length of the rolling interval.
+    pub interval: Option<Duration>,
+    /// The number of requests allowed per second.
+    pub secondly_max: Option<u32>,
+    /// The number of requests remaining in the current second.
+    pub secondly_remaining: Option<u32>,
+    /// The number of requests allowed per day. Reported only, not enforced by the limiter.
+    pub daily_max: Option<u32>,
+    /// The number of requests remaining for the day. Reported only, not enforced by the limiter.
+    pub daily_remaining: Option<u32>,
+    /// When the quota was last reported.
+    pub updated_at: Option<Instant>,
+}
+
+/// A token bucket refilled continuously over its interval.
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    interval: Duration,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(capacity: u32, interval: Duration) -> Self {
+        Self {
+            capacity: capacity as f64,
+            tokens: capacity as f64,
+            interval,
+            refilled_at: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.refilled_at).as_secs_f64();
+        let rate = self.capacity / self.interval.as_secs_f64().max(f64::EPSILON);
+
+        self.tokens = (self.tokens + elapsed * rate).min(self.capacity);
+        self.refilled_at = now;
+    }
+
+    /// Takes a token, or returns how long until one is available.
+    fn try_take(&mut self) -> Result<(), Duration> {
+        self.refill();
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            let rate = self.capacity / self.interval.as_secs_f64().max(f64::EPSILON);
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / rate))
+        }
+    }
+
+    /// Resize the bucket to the quota reported by Hubspot.
+    fn resize(&mut self, capacity: u32, interval: Duration) {
+        self.refill();
+        self.capacity = capacity.max(1) as f64;
+        self.interval = interval;
+        self.tokens = self.tokens.min(self.capacity);
+    }
+
+    /// Never hold more tokens than Hubspot reports as remaining.
+    fn clamp(&mut self, remaining: u32) {
+        self.refill();
+        self.tokens = self.tokens.min(remaining as f64);
+    }
+}
+
+/// Throttles requests sent through a [HubspotClient](super::HubspotClient).
+///
+/// The limiter lives on the client, so it is shared by every api collection built from the same client.
+#[derive(Debug)]
+pub struct RateLimiter {
+    throttle: bool,
+    general: Mutex<TokenBucket>,
+    search: Mutex<TokenBucket>,
+    snapshot: Mutex<RateLimitSnapshot>,
+}
+
+impl RateLimiter {
+    /// Create a rate limiter from its configuration.
+    pub fn new(config: RateLimit) -> Self {
+        Self {
+            throttle: config.throttle,
+            general: Mutex::new(TokenBucket::new(config.requests, config.interval)),
+            search: Mutex::new(TokenBucket::new(
+                config.search_requests,
+                config.search_interval,
+            )),
+            snapshot: Mutex::new(RateLimitSnapshot::default()),
+        }
+    }
+
+    /// Wait until the request is allowed to be sent.
+    pub async fn acquire(&self, search: bool) {
+        if !self.throttle {
+            return;
+        }
+
+        let bucket = if search { &self.search } else { &self.general };
+        loop {
+            let wait = bucket.lock().unwrap().try_take();
+            match wait {
+                Ok(()) => return,
+                Err(wait) => tokio::time::sleep(wait).await,
+            }
+        }
+    }
+
+    /// Update the limiter from the rate limit headers of a response.
+    pub fn observe(&self, headers: &HeaderMap, search: bool, rate_limited: bool) {
+        if rate_limited {
+            let bucket = if search { &self.search } else { &self.general };
+            bucket.lock().unwrap().clamp(0);
+        }
+
+        let max = header_value(headers, HEADER_MAX);
+        let remaining = header_value(headers, HEADER_REMAINING);
+        let interval =
+            header_value(headers, HEADER_INTERVAL).map(|ms| Duration::from_millis(ms as u64));
+
+        {
+            let mut general = self.general.lock().unwrap();
+            if let (Some(max), Some(interval)) = (max, interval) {
+                general.resize(max, interval);
+            }
+            if let Some(remaining) = remaining {
+                general.clamp(remaining);
+            }
+        }
+
+        if max.is_none() && remaining.is_none() && headers.get(HEADER_DAILY).is_none() {
+            return;
+        }
+
+        let mut snapshot = self.snapshot.lock().unwrap();
+        *snapshot = RateLimitSnapshot {
+            interval_max: max,
+            interval_remaining: remaining,
+            interval,
+            secondly_max: header_value(headers, HEADER_SECONDLY),
+            secondly_remaining: header_value(headers, HEADER_SECONDLY_REMAINING),
+            daily_max: header_value(headers, HEADER_DAILY),
+            daily_remaining: header_value(headers, HEADER_DAILY_REMAINING),
+            updated_at: Some(Instant::now()),
+        };
+    }
+
+    /// The most recent quota reported by Hubspot.
+    pub fn snapshot(&self) -> RateLimitSnapshot {
+        self.snapshot.lock().unwrap().clone()
+    }
+}
+
+fn header_value(headers: &HeaderMap, name: &str) -> Option<u32> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::HeaderValue;
+
+    use super::*;
+
+    fn headers(values: &[(&'static str, &'static str)]) -> HeaderMap {
+        values
+            .iter()
+            .map(|(name, value)| (name.parse().unwrap(), HeaderValue::from_static(value)))
+            .collect()
+    }
+
+    #[test]
+    fn token_bucket_waits_once_empty() {
+        let mut bucket = TokenBucket::new(2, Duration::from_secs(10));
+
+        assert!(bucket.try_take().is_ok());
+        assert!(bucket.try_take().is_ok());
+        let wait = bucket.try_take().unwrap_err();
+        assert!(wait > Duration::from_secs(4) && wait <= Duration::from_secs(5));
+    }
+
+    #[test]
+    fn token_bucket_is_resized_and_clamped_to_the_reported_quota() {
+        let mut bucket = TokenBucket::new(100, Duration::from_secs(10));
+
+        bucket.resize(10, Duration::from_secs(1));
+        assert!(bucket.tokens <= 10.0);
+
+        bucket.clamp(1);
+        assert!(bucket.try_take().is_ok());
+        assert!(bucket.try_take().is_err());
+    }
+
+    #[tokio::test]
+    async fn search_requests_have_their_own_bucket() {
+        let limiter = RateLimiter::new(
+            RateLimit::new()
+                .requests(1, Duration::from_secs(60))
+                .search_requests(1, Duration::from_secs(60)),
+        );
+
+        limiter.acquire(false).await;
+        limiter.acquire(true).await;
+        assert!(limiter.general.lock().unwrap().try_take().is_err());
+        assert!(limiter.search.lock().unwrap().try_take().is_err());
+    }
+
+    #[test]
+    fn rate_limited_responses_empty_the_bucket() {
+        let limiter = RateLimiter::new(RateLimit::new());
+
+        limiter.observe(&HeaderMap::new(), true, true);
+
+        assert!(limiter.search.lock().unwrap().try_take().is_err());
+        assert!(limiter.general.lock().unwrap().try_take().is_ok());
+    }
+
+    #[test]
+    fn reports_the_quota_from_the_headers() {
+        let limiter = RateLimiter::new(RateLimit::new());
+
+        limiter.observe(
+            &headers(&[
+                (HEADER_MAX, "190"),
+                (HEADER_REMAINING, "0"),
+                (HEADER_INTERVAL, "10000"),
+                (HEADER_DAILY, "250000"),
+                (HEADER_DAILY_REMAINING, "249000"),
+            ]),
+            false,
+            false,
+        );
+
+        let snapshot = limiter.snapshot();
+        assert_eq!(snapshot.interval_max, Some(190));
+        assert_eq!(snapshot.interval, Some(Duration::from_secs(10)));
+        assert_eq!(snapshot.daily_max, Some(250000));
+        assert_eq!(snapshot.daily_remaining, Some(249000));
+        assert!(limiter.general.lock().unwrap().try_take().is_err());
+    }
+}
@@ -0,0 +1,243 @@
+//! Retry policy for hubspot requests.
+use std::time::Duration;
+
+use reqwest::{header::RETRY_AFTER, Method, StatusCode};
+
+use super::error::{HubspotError, HubspotResult};
+use super::transport::{HttpRequest, HttpResponse};
+
+/// Controls how failed requests are retried by the client.
+///
+/// Rate limited (429) responses are always retried, as Hubspot has not processed the request.
+/// Server errors (5xx) and connection failures are only retried for requests that are safe
+/// to repeat, being `GET` requests, batch reads and searches.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: bool,
+    respect_retry_after: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+            respect_retry_after: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Create a retry policy with the default settings.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// A policy that sends every request exactly once.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// The maximum number of attempts, including the first request.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// The delay before the first retry. Doubles with every following attempt.
+    pub fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// The upper bound of the delay between two attempts.
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Whether to randomise the backoff delay to spread out retries from concurrent workers.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Whether to wait for the duration of Hubspot's `Retry-After` header when present.
+    pub fn respect_retry_after(mut self, respect_retry_after: bool) -> Self {
+        self.respect_retry_after = respect_retry_after;
+        self
+    }
+
+    /// Returns the delay before the next attempt, or `None` if the request should not be retried.
+    pub(crate) fn delay_for(
+        &self,
+        outcome: &HubspotResult<HttpResponse>,
+        attempts: u32,
+        idempotent: bool,
+    ) -> Option<Duration> {
+        if attempts >= self.max_attempts {
+            return None;
+        }
+
+        match outcome {
+            Ok(res)
+                if res.status == StatusCode::TOO_MANY_REQUESTS
+                    || (res.status.is_server_error() && idempotent) =>
+            {
+                Some(
+                    self.retry_after(res)
+                        .unwrap_or_else(|| self.backoff(attempts)),
+                )
+            }
+            Err(HubspotError::Http(err))
+                if idempotent && (err.is_timeout() || err.is_connect()) =>
+            {
+                Some(self.backoff(attempts))
+            }
+            _ => None,
+        }
+    }
+
+    /// Exponential backoff for the given attempt number, with equal jitter when enabled.
+    pub(crate) fn backoff(&self, attempts: u32) -> Duration {
+        let exponent = attempts.saturating_sub(1).min(16);
+        let delay = self
+            .base_delay
+            .saturating_mul(1 << exponent)
+            .min(self.max_delay);
+
+        if self.jitter {
+            let half = delay / 2;
+            half + half.mul_f64(fastrand::f64())
+        } else {
+            delay
+        }
+    }
+
+    /// The delay requested by Hubspot through the `Retry-After` header, in seconds.
+    fn retry_after(&self, res: &HttpResponse) -> Option<Duration> {
+        if !self.respect_retry_after {
+            return None;
+        }
+
+        res.headers
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .map(|seconds| Duration::from_secs(seconds).min(self.max_delay))
+    }
+}
+
+/// Whether a request can safely be sent again after a server error.
+pub(crate) fn is_idempotent(req: &HttpRequest) -> bool {
+    match req.method {
+        Method::GET | Method::HEAD => true,
+        Method::POST => {
+            let path = req.url.path();
+            path.ends_with("/batch/read") || path.ends_with("/search")
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::HeaderValue;
+    use reqwest::Client;
+
+    use super::*;
+
+    fn response(
+        status: StatusCode,
+        retry_after: Option<&'static str>,
+    ) -> HubspotResult<HttpResponse> {
+        let mut res = HttpResponse::new(status, Vec::new());
+        if let Some(retry_after) = retry_after {
+            res.headers
+                .insert(RETRY_AFTER, HeaderValue::from_static(retry_after));
+        }
+        Ok(res)
+    }
+
+    fn request(method: Method, path: &str) -> HttpRequest {
+        let url = format!("https://api.hubapi.com/{path}");
+        HttpRequest::from_request(Client::new().request(method, url).build().unwrap())
+    }
+
+    #[test]
+    fn waits_for_the_retry_after_header() {
+        let policy = RetryPolicy::new().max_delay(Duration::from_secs(5));
+        let limited = response(StatusCode::TOO_MANY_REQUESTS, Some("2"));
+        let too_long = response(StatusCode::TOO_MANY_REQUESTS, Some("60"));
+
+        assert_eq!(
+            policy.delay_for(&limited, 1, false),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(
+            policy.delay_for(&too_long, 1, false),
+            Some(Duration::from_secs(5))
+        );
+    }
+
+    #[test]
+    fn backs_off_exponentially_without_a_retry_after_header() {
+        let policy = RetryPolicy::new()
+            .base_delay(Duration::from_millis(100))
+            .max_delay(Duration::from_millis(300))
+            .jitter(false);
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(300));
+        assert_eq!(
+            policy.delay_for(&response(StatusCode::BAD_GATEWAY, None), 2, true),
+            Some(Duration::from_millis(200))
+        );
+    }
+
+    #[test]
+    fn retries_server_errors_of_idempotent_requests_only() {
+        let policy = RetryPolicy::new();
+        let failed = response(StatusCode::INTERNAL_SERVER_ERROR, None);
+
+        assert!(policy.delay_for(&failed, 1, true).is_some());
+        assert!(policy.delay_for(&failed, 1, false).is_none());
+        assert!(policy
+            .delay_for(&response(StatusCode::BAD_REQUEST, None), 1, true)
+            .is_none());
+        assert!(policy.delay_for(&failed, 3, true).is_none());
+    }
+
+    #[test]
+    fn reads_and_searches_are_idempotent() {
+        assert!(is_idempotent(&request(
+            Method::GET,
+            "crm/v3/objects/contacts"
+        )));
+        assert!(is_idempotent(&request(
+            Method::POST,
+            "crm/v3/objects/contacts/batch/read"
+        )));
+        assert!(is_idempotent(&request(
+            Method::POST,
+            "crm/v3/objects/contacts/search"
+        )));
+        assert!(!is_idempotent(&request(
+            Method::POST,
+            "crm/v3/objects/contacts"
+        )));
+        assert!(!is_idempotent(&request(
+            Method::PATCH,
+            "crm/v3/objects/contacts/1"
+        )));
+    }
+}
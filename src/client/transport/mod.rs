@@ -0,0 +1,180 @@
+//! The HTTP transport used to send requests to Hubspot.
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use reqwest::{header::HeaderMap, Client, Method, Request, StatusCode, Url};
+
+use super::error::HubspotResult;
+
+#[cfg(any(test, feature = "test-util"))]
+mod test_util;
+
+#[cfg(any(test, feature = "test-util"))]
+pub use test_util::{MockTransport, RecordingTransport};
+
+/// A request to be sent by a [HttpTransport].
+#[derive(Clone, Debug)]
+pub struct HttpRequest {
+    /// The HTTP request method.
+    pub method: Method,
+    /// The full request url, including the query string.
+    pub url: Url,
+    /// The request headers, including authorization.
+    pub headers: HeaderMap,
+    /// The request body, if any.
+    pub body: Option<Vec<u8>>,
+}
+
+impl HttpRequest {
+    /// Convert a built reqwest request into a transport request.
+    pub(crate) fn from_request(request: Request) -> Self {
+        let body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .map(|body| body.to_vec());
+
+        Self {
+            method: request.method().clone(),
+            url: request.url().clone(),
+            headers: request.headers().clone(),
+            body,
+        }
+    }
+}
+
+/// A response returned by a [HttpTransport].
+#[derive(Clone, Debug)]
+pub struct HttpResponse {
+    /// The HTTP status of the response.
+    pub status: StatusCode,
+    /// The response headers.
+    pub headers: HeaderMap,
+    /// The response body.
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// Create a response with a status and body, and no headers.
+    pub fn new(status: StatusCode, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers: HeaderMap::new(),
+            body: body.into(),
+        }
+    }
+}
+
+/// Sends the HTTP requests of a [Hubspot](crate::Hubspot) client.
+///
+/// The default transport sends requests with reqwest. Implement this trait to run the client
+/// against a fake, eg. the transports of the `test-util` feature.
+#[async_trait]
+pub trait HttpTransport: Debug + Send + Sync {
+    /// Send a request and return the response, whatever its status.
+    async fn send(&self, request: HttpRequest) -> HubspotResult<HttpResponse>;
+}
+
+/// The default transport, backed by a reqwest client.
+#[derive(Clone, Debug, Default)]
+pub struct ReqwestTransport {
+    client: Client,
+}
+
+impl ReqwestTransport {
+    /// Create a transport sending requests with the given reqwest client.
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl HttpTransport for ReqwestTransport {
+    async fn send(&self, request: HttpRequest) -> HubspotResult<HttpResponse> {
+        let mut req = self
+            .client
+            .request(request.method, request.url)
+            .headers(request.headers);
+        if let Some(body) = request.body {
+            req = req.body(body);
+        }
+
+        let res = req.send().await?;
+        let status = res.status();
+        let headers = res.headers().clone();
+        let body = res.bytes().await?.to_vec();
+
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::CONTENT_TYPE;
+    use serde_json::json;
+
+    use super::*;
+    use crate::client::error::HubspotError;
+
+    fn request() -> HttpRequest {
+        let request = Client::new()
+            .post("https://api.hubapi.com/crm/v3/objects/contacts?archived=false")
+            .json(&json!({ "properties": { "email": "bh@hubspot.com" } }))
+            .build()
+            .unwrap();
+        HttpRequest::from_request(request)
+    }
+
+    #[test]
+    fn converts_reqwest_requests() {
+        let request = request();
+
+        assert_eq!(request.method, Method::POST);
+        assert_eq!(request.url.query(), Some("archived=false"));
+        assert_eq!(request.headers[CONTENT_TYPE], "application/json");
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&request.body.unwrap()).unwrap(),
+            json!({ "properties": { "email": "bh@hubspot.com" } })
+        );
+    }
+
+    #[tokio::test]
+    async fn mock_transport_responds_in_order() {
+        let transport = MockTransport::new();
+        transport
+            .respond(HttpResponse::new(StatusCode::CREATED, "created"))
+            .respond(HttpResponse::new(StatusCode::NO_CONTENT, Vec::new()));
+
+        assert_eq!(
+            transport.send(request()).await.unwrap().status,
+            StatusCode::CREATED
+        );
+        assert_eq!(
+            transport.send(request()).await.unwrap().status,
+            StatusCode::NO_CONTENT
+        );
+        assert!(matches!(
+            transport.send(request()).await,
+            Err(HubspotError::UnexpectedResponse(_))
+        ));
+        assert_eq!(transport.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn recording_transport_passes_requests_on() {
+        let inner = MockTransport::new();
+        inner.respond(HttpResponse::new(StatusCode::OK, "{}"));
+        let transport = RecordingTransport::new(inner);
+
+        let response = transport.send(request()).await.unwrap();
+
+        assert_eq!(response.body, b"{}");
+        assert_eq!(
+            transport.requests()[0].url.path(),
+            "/crm/v3/objects/contacts"
+        );
+    }
+}
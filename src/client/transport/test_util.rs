@@ -0,0 +1,95 @@
+//! Transports for testing code built on the client without touching the network.
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use reqwest::StatusCode;
+use serde::Serialize;
+
+use super::{HttpRequest, HttpResponse, HttpTransport};
+use crate::client::error::{HubspotError, HubspotResult};
+
+/// A transport that returns canned responses in the order they were added,
+/// and records every request it receives.
+#[derive(Debug, Default)]
+pub struct MockTransport {
+    responses: Mutex<VecDeque<HttpResponse>>,
+    requests: Mutex<Vec<HttpRequest>>,
+}
+
+impl MockTransport {
+    /// Create a transport with no canned responses.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Queue a response.
+    pub fn respond(&self, response: HttpResponse) -> &Self {
+        self.responses.lock().unwrap().push_back(response);
+        self
+    }
+
+    /// Queue a response with a json body.
+    pub fn respond_json<B>(&self, status: StatusCode, body: &B) -> &Self
+    where
+        B: Serialize + ?Sized,
+    {
+        let body = serde_json::to_vec(body).expect("The response body serializes to json");
+        self.respond(HttpResponse::new(status, body))
+    }
+
+    /// The requests received so far.
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl HttpTransport for MockTransport {
+    async fn send(&self, request: HttpRequest) -> HubspotResult<HttpResponse> {
+        let description = format!("{} {}", request.method, request.url);
+        self.requests.lock().unwrap().push(request);
+
+        self.responses.lock().unwrap().pop_front().ok_or_else(|| {
+            HubspotError::UnexpectedResponse(format!(
+                "MockTransport has no response for {description}"
+            ))
+        })
+    }
+}
+
+/// A transport that records every request before passing it to another transport.
+#[derive(Debug)]
+pub struct RecordingTransport<T> {
+    inner: T,
+    requests: Mutex<Vec<HttpRequest>>,
+}
+
+impl<T> RecordingTransport<T>
+where
+    T: HttpTransport,
+{
+    /// Record the requests sent through the inner transport.
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            requests: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The requests sent so far.
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl<T> HttpTransport for RecordingTransport<T>
+where
+    T: HttpTransport,
+{
+    async fn send(&self, request: HttpRequest) -> HubspotResult<HttpResponse> {
+        self.requests.lock().unwrap().push(request.clone());
+        self.inner.send(request).await
+    }
+}
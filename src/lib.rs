@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use builder::HubspotBuilder;
+pub use builder::Region;
 use client::HubspotClient;
 use engagements::EngagementsManager;
 use objects::ObjectsManager;
@@ -10,17 +11,46 @@ mod builder;
 mod client;
 mod engagements;
 mod objects;
-mod owners;
+pub mod owners;
+pub mod pipelines;
+pub mod properties;
+pub mod schemas;
 
 pub mod associations {
-    pub use super::api_configs::{AssociationCreationDetails, AssociationTypes};
+    pub use super::api_configs::{
+        Association, AssociationBatchResult, AssociationCreationDetails, AssociationObjectId,
+        AssociationPair, AssociationTypes, CreatedAssociationResult, DefaultAssociation,
+        PrimaryCompanyChange, RecordAssociations,
+    };
 }
 
+pub mod batch {
+    pub use super::api_configs::batch::{
+        BatchErrorPolicy, BatchOptions, BatchResult, BatchUpdateOutcome, MAX_BATCH_SIZE,
+    };
+}
+
+pub mod search {
+    pub use super::api_configs::search::{
+        Filter, FilterGroup, FilterOperator, SearchRequest, SearchResult, Sort, SortDirection,
+    };
+}
+
+pub use api_configs::paging::PagingOptions;
 pub use api_configs::types;
+pub use client::error;
+pub use client::oauth;
+pub use client::rate_limit::{RateLimit, RateLimitSnapshot};
+pub use client::retry::RetryPolicy;
+pub use client::transport;
 pub use engagements::notes;
 pub use engagements::EngagementType;
+pub use objects::tickets;
 pub use objects::ObjectType;
 use owners::OwnerApi;
+use pipelines::PipelinesApi;
+use properties::PropertiesApi;
+use schemas::SchemasApi;
 
 // A Rust implementation of the Hubspot CRM API
 #[derive(Clone, Debug)]
@@ -32,6 +62,13 @@ pub struct Hubspot {
     pub engagements: EngagementsManager,
     /// Owners are specific users assigned to contacts, companies, deals, tickets, or engagements.
     pub owners: OwnerApi,
+    /// Pipelines track records such as deals and tickets through stages.
+    pub pipelines: PipelinesApi,
+    /// Properties store information about records.
+    pub properties: PropertiesApi,
+    /// Schemas define the custom object types of the account.
+    pub schemas: SchemasApi,
+    client: Arc<HubspotClient>,
 }
 
 impl Hubspot {
@@ -45,9 +82,19 @@ impl Hubspot {
             objects: ObjectsManager::new(Arc::clone(&client)),
             engagements: EngagementsManager::new(Arc::clone(&client)),
             owners: OwnerApi::new(Arc::clone(&client)),
+            pipelines: PipelinesApi::new(Arc::clone(&client)),
+            properties: PropertiesApi::new(Arc::clone(&client)),
+            schemas: SchemasApi::new(Arc::clone(&client)),
+            client,
         }
     }
 
+    /// The most recent rate limit quota reported by Hubspot.
+    /// Shared by every api collection of this client.
+    pub fn rate_limit(&self) -> RateLimitSnapshot {
+        self.client.rate_limit()
+    }
+
     /// Create Hubspot client
     pub fn builder() -> HubspotBuilder {
         HubspotBuilder::new()
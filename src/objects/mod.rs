@@ -0,0 +1,193 @@
+pub mod tickets;
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use strum_macros::Display;
+
+use crate::api_configs::types::ToPath;
+use crate::api_configs::ApiCollection;
+use crate::client::HubspotClient;
+use crate::schemas::ObjectSchema;
+
+#[derive(Clone, Debug, Display)]
+pub enum ObjectType {
+    Contacts,
+    Companies,
+    Deals,
+    LineItems,
+    Tickets,
+    /// A custom object, identified by its object type ID (eg. `2-123456`) or fully qualified name (eg. `p123456_projects`).
+    Custom(String),
+}
+
+// TODO see if we can use strum
+impl ToPath for ObjectType {
+    fn to_path(&self) -> String {
+        match self {
+            ObjectType::LineItems => "line_items".to_string(),
+            ObjectType::Custom(object_type) => object_type.clone(),
+            object_type => object_type.to_string().to_lowercase(),
+        }
+    }
+}
+
+/// Objects represent types of relationships or processes.
+///
+/// All HubSpot accounts include four standard objects: contacts, companies, deals, and tickets.
+/// Depending on your HubSpot subscription, there are additional objects, such as products and custom objects.
+///
+/// Records are individual instances of an object (e.g., John Smith is a contact). For each record, you can store information in properties, track interactions, and create reports. You can also make associations between records to understand the relationships between them
+#[derive(Clone, Debug)]
+pub struct ObjectsManager {
+    /// Contacts store information about an individual person.
+    pub contacts: ApiCollection<ObjectType>,
+    /// Companies store information about an individual business or organization.
+    pub companies: ApiCollection<ObjectType>,
+    /// Deals represent revenue opportunities with a contact or company. They’re tracked through pipeline stages, resulting in the deal being won or lost.
+    pub deals: ApiCollection<ObjectType>,
+    /// Line items are individual instances of products. When a product is attached to a deal, it becomes a line item.
+    pub line_items: ApiCollection<ObjectType>,
+    /// Tickets represent customer requests for help. They're tracked through pipeline stages until they're closed.
+    pub tickets: ApiCollection<ObjectType>,
+    /// Custom object collections registered by name.
+    custom: Arc<RwLock<HashMap<String, ApiCollection<ObjectType>>>>,
+    client: Arc<HubspotClient>,
+}
+
+impl ObjectsManager {
+    pub fn new(client: Arc<HubspotClient>) -> Self {
+        Self {
+            contacts: ApiCollection::new(ObjectType::Contacts, Arc::clone(&client)),
+            companies: ApiCollection::new(ObjectType::Companies, Arc::clone(&client)),
+            deals: ApiCollection::new(ObjectType::Deals, Arc::clone(&client)),
+            line_items: ApiCollection::new(ObjectType::LineItems, Arc::clone(&client)),
+            tickets: ApiCollection::new(ObjectType::Tickets, Arc::clone(&client)),
+            custom: Arc::new(RwLock::new(HashMap::new())),
+            client,
+        }
+    }
+
+    /// Returns the collection of a custom object.
+    ///
+    /// `object_type` is either a name registered with [ObjectsManager::register_custom],
+    /// or the object type ID (eg. `2-123456`) or fully qualified name of the custom object.
+    ///
+    /// The name is not checked against the account's schemas: an unknown custom object
+    /// only fails once a request is sent.
+    pub fn custom(&self, object_type: &str) -> ApiCollection<ObjectType> {
+        if let Some(collection) = self.custom.read().unwrap().get(object_type) {
+            return collection.clone();
+        }
+
+        ApiCollection::new(
+            ObjectType::Custom(object_type.to_owned()),
+            Arc::clone(&self.client),
+        )
+    }
+
+    /// Register a custom object under a name, to look its collection up with [ObjectsManager::custom].
+    pub fn register_custom(&self, name: &str, object_type_id: &str) -> ApiCollection<ObjectType> {
+        let collection = ApiCollection::new(
+            ObjectType::Custom(object_type_id.to_owned()),
+            Arc::clone(&self.client),
+        );
+        self.custom
+            .write()
+            .unwrap()
+            .insert(name.to_owned(), collection.clone());
+
+        collection
+    }
+
+    /// Register a custom object by its schema's name, returning the collection of its records.
+    pub fn register_schema(&self, schema: &ObjectSchema) -> ApiCollection<ObjectType> {
+        self.register_custom(&schema.name, &schema.object_type_id)
+    }
+
+    /// Uses the object_type to return the relevant collection.
+    ///
+    /// # Panics
+    ///
+    /// Custom objects have no collection to borrow, so this panics for [ObjectType::Custom].
+    /// Use [ObjectsManager::collection] or [ObjectsManager::custom] for custom objects.
+    pub fn get_collection(&self, object_type: ObjectType) -> &ApiCollection<ObjectType> {
+        match object_type {
+            ObjectType::Contacts => &self.contacts,
+            ObjectType::Companies => &self.companies,
+            ObjectType::Deals => &self.deals,
+            ObjectType::LineItems => &self.line_items,
+            ObjectType::Tickets => &self.tickets,
+            ObjectType::Custom(object_type) => panic!(
+                "The custom object {object_type} has no collection to borrow, use ObjectsManager::collection"
+            ),
+        }
+    }
+
+    /// Returns the collection of any object type, including custom objects.
+    ///
+    /// Custom objects are looked up as in [ObjectsManager::custom].
+    pub fn collection(&self, object_type: ObjectType) -> ApiCollection<ObjectType> {
+        match object_type {
+            ObjectType::Custom(object_type) => self.custom(&object_type),
+            object_type => self.get_collection(object_type).clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::Client;
+
+    use super::*;
+    use crate::api_configs::types::ObjectApi;
+    use crate::client::Auth;
+
+    fn objects() -> ObjectsManager {
+        ObjectsManager::new(Arc::new(HubspotClient::new(
+            Client::new(),
+            "https://api.hubapi.com",
+            Auth::Token("token".to_owned()),
+            "1",
+        )))
+    }
+
+    #[test]
+    fn borrows_the_collections_of_standard_objects() {
+        let objects = objects();
+
+        assert!(std::ptr::eq(
+            objects.get_collection(ObjectType::Deals),
+            &objects.deals
+        ));
+        assert_eq!(
+            objects.get_collection(ObjectType::LineItems).path(),
+            "line_items"
+        );
+    }
+
+    #[test]
+    fn looks_custom_objects_up_by_registered_name() {
+        let objects = objects();
+        objects.register_custom("projects", "2-123456");
+
+        assert_eq!(objects.custom("projects").path(), "2-123456");
+        assert_eq!(
+            objects
+                .collection(ObjectType::Custom("projects".to_owned()))
+                .path(),
+            "2-123456"
+        );
+        assert_eq!(
+            objects.custom("p123456_projects").path(),
+            "p123456_projects"
+        );
+        assert_eq!(objects.collection(ObjectType::Tickets).path(), "tickets");
+    }
+
+    #[test]
+    #[should_panic]
+    fn cannot_borrow_custom_collections() {
+        objects().get_collection(ObjectType::Custom("2-123456".to_owned()));
+    }
+}
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+/// The urgency of a ticket.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TicketPriority {
+    Low,
+    Medium,
+    High,
+    Urgent,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TicketProperties {
+    /// The name of the ticket.
+    pub subject: String,
+    /// The ticket's description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// The ID of the pipeline the ticket is tracked through. Required to create a ticket.
+    #[serde(rename = "hs_pipeline")]
+    pub pipeline: String,
+    /// The ID of the ticket's status, a stage of its pipeline. Required to create a ticket.
+    #[serde(rename = "hs_pipeline_stage")]
+    pub stage: String,
+    /// The urgency of the ticket.
+    #[serde(rename = "hs_ticket_priority", skip_serializing_if = "Option::is_none")]
+    pub priority: Option<TicketPriority>,
+}
+
+impl TicketProperties {
+    /// Create the properties of a new ticket in the given pipeline and stage.
+    pub fn new(subject: String, pipeline: String, stage: String) -> Self {
+        Self {
+            subject,
+            content: None,
+            pipeline,
+            stage,
+            priority: None,
+        }
+    }
+}
@@ -1,12 +1,19 @@
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use serde::Deserialize;
 use time::OffsetDateTime;
+use tokio::sync::Mutex;
 
 use crate::client::HubspotClient;
 
+use futures::{Stream, TryStreamExt};
 use reqwest::Method;
 
+use crate::api_configs::paging::{paginate, PagingOptions};
+use crate::api_configs::query::Query;
+use crate::api_configs::types::ListResult;
 use crate::client::error::HubspotResult;
 
 /// HubSpot uses owners to assign specific users to contacts, companies, deals, tickets,
@@ -15,7 +22,7 @@ use crate::client::error::HubspotResult;
 /// this purpose. Owners can only be created in HubSpot, but you can use the owners endpoints
 ///  to get their identifying details, including IDs and email addresses. This data can
 /// then be assigned to CRM records in HubSpot or via property change API calls.
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Owner {
     /// Owner Identifier
@@ -41,7 +48,7 @@ pub struct Owner {
     pub teams: Option<Vec<Team>>,
 }
 
-#[derive(Deserialize, Debug, Default)]
+#[derive(Deserialize, Debug, Clone, Default)]
 pub struct Team {
     /// The team identifier
     pub id: String,
@@ -75,11 +82,249 @@ impl OwnerApi {
             .send::<Owner>(self.client.begin(
                 Method::GET,
                 &format!(
-                    "crm/v3/owners/{}?archived={}",
+                    "crm/v3/owners/{}{}",
                     id,
-                    archived.unwrap_or(false)
+                    Query::new().push("archived", archived.unwrap_or(false))
                 ),
             ))
             .await
     }
+
+    /// Returns a page of the owners of the account, optionally only the owner with the given email.
+    pub async fn list(
+        &self,
+        email: Option<&str>,
+        limit: Option<i32>,
+        after: Option<&str>,
+        archived: Option<bool>,
+    ) -> HubspotResult<ListResult<Owner>> {
+        self.client
+            .send::<ListResult<Owner>>(self.client.begin(
+                Method::GET,
+                &format!(
+                    "crm/v3/owners{}",
+                    Query::new()
+                        .push_opt("email", email)
+                        .push_opt("limit", limit)
+                        .push_opt("after", after)
+                        .push("archived", archived.unwrap_or(false))
+                ),
+            ))
+            .await
+    }
+
+    /// Stream every owner of the account, fetching pages lazily as the stream is consumed.
+    pub fn list_all(
+        &self,
+        email: Option<&str>,
+        options: PagingOptions,
+        archived: Option<bool>,
+    ) -> impl Stream<Item = HubspotResult<Owner>> {
+        let owners = self.clone();
+        let email = email.map(str::to_owned);
+        paginate(options, move |limit, after| {
+            let owners = owners.clone();
+            let email = email.clone();
+            async move {
+                owners
+                    .list(email.as_deref(), limit, after.as_deref(), archived)
+                    .await
+            }
+        })
+    }
+
+    /// Create a directory of the active owners, cached for the given time to live.
+    pub fn directory(&self, ttl: Duration) -> OwnerDirectory {
+        OwnerDirectory::new(self.clone(), ttl)
+    }
+}
+
+/// The owners of the account, indexed when they were loaded.
+#[derive(Debug)]
+struct OwnerIndex {
+    loaded_at: Instant,
+    by_id: HashMap<String, Owner>,
+    by_email: HashMap<String, String>,
+    by_user_id: HashMap<i64, String>,
+}
+
+impl OwnerIndex {
+    fn new(owners: Vec<Owner>) -> Self {
+        let mut index = Self {
+            loaded_at: Instant::now(),
+            by_id: HashMap::with_capacity(owners.len()),
+            by_email: HashMap::with_capacity(owners.len()),
+            by_user_id: HashMap::with_capacity(owners.len()),
+        };
+        for owner in owners {
+            index
+                .by_email
+                .insert(owner.email.to_lowercase(), owner.id.clone());
+            index.by_user_id.insert(owner.user_id, owner.id.clone());
+            index.by_id.insert(owner.id.clone(), owner);
+        }
+        index
+    }
+}
+
+/// A cached directory of the active owners of the account, to look owners up by email or user ID.
+///
+/// Every owner is loaded on first use, and reloaded on the first lookup after the time to live
+/// has passed. Clones of the directory share the same cache.
+#[derive(Clone, Debug)]
+pub struct OwnerDirectory {
+    owners: OwnerApi,
+    ttl: Duration,
+    index: Arc<Mutex<Option<OwnerIndex>>>,
+}
+
+impl OwnerDirectory {
+    /// Create an empty directory. Owners are loaded on the first lookup.
+    pub fn new(owners: OwnerApi, ttl: Duration) -> Self {
+        Self {
+            owners,
+            ttl,
+            index: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Returns the owner with the given ID.
+    pub async fn by_id(&self, id: &str) -> HubspotResult<Option<Owner>> {
+        self.lookup(|index| index.by_id.get(id).cloned()).await
+    }
+
+    /// Returns the owner with the given email, ignoring case.
+    pub async fn by_email(&self, email: &str) -> HubspotResult<Option<Owner>> {
+        let email = email.to_lowercase();
+        self.lookup(|index| {
+            index
+                .by_email
+                .get(&email)
+                .and_then(|id| index.by_id.get(id))
+                .cloned()
+        })
+        .await
+    }
+
+    /// Returns the owner with the given user ID.
+    pub async fn by_user_id(&self, user_id: i64) -> HubspotResult<Option<Owner>> {
+        self.lookup(|index| {
+            index
+                .by_user_id
+                .get(&user_id)
+                .and_then(|id| index.by_id.get(id))
+                .cloned()
+        })
+        .await
+    }
+
+    /// Clear the cache, so the owners are reloaded on the next lookup.
+    pub async fn invalidate(&self) {
+        *self.index.lock().await = None;
+    }
+
+    async fn lookup<F>(&self, find: F) -> HubspotResult<Option<Owner>>
+    where
+        F: FnOnce(&OwnerIndex) -> Option<Owner>,
+    {
+        let mut index = self.index.lock().await;
+        let expired = index
+            .as_ref()
+            .map_or(true, |index| index.loaded_at.elapsed() >= self.ttl);
+        if expired {
+            let owners = self
+                .owners
+                .list_all(None, PagingOptions::new().page_size(500), Some(false))
+                .try_collect()
+                .await?;
+            *index = Some(OwnerIndex::new(owners));
+        }
+
+        Ok(index.as_ref().and_then(find))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::{Client, StatusCode};
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::client::transport::MockTransport;
+    use crate::client::Auth;
+
+    fn owners(transport: &Arc<MockTransport>) -> OwnerApi {
+        OwnerApi::new(Arc::new(
+            HubspotClient::new(
+                Client::new(),
+                "https://api.hubapi.com",
+                Auth::Token("token".to_owned()),
+                "1",
+            )
+            .with_transport(transport.clone()),
+        ))
+    }
+
+    fn page() -> Value {
+        json!({
+            "results": [{
+                "id": "101",
+                "email": "Owner@Hubspot.com",
+                "firstName": "Jane",
+                "lastName": "Doe",
+                "userId": 9001,
+                "createdAt": "2023-01-01T00:00:00Z",
+                "updatedAt": "2023-01-01T00:00:00Z",
+                "archived": false,
+            }],
+        })
+    }
+
+    #[tokio::test]
+    async fn loads_the_owners_once_within_the_ttl() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond_json(StatusCode::OK, &page());
+        let directory = owners(&transport).directory(Duration::from_secs(60));
+
+        let owner = directory.by_email("owner@hubspot.com").await.unwrap();
+        assert_eq!(owner.unwrap().id, "101");
+        assert_eq!(
+            directory.by_user_id(9001).await.unwrap().unwrap().email,
+            "Owner@Hubspot.com"
+        );
+        assert!(directory.by_id("102").await.unwrap().is_none());
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].url.query(), Some("limit=500&archived=false"));
+    }
+
+    #[tokio::test]
+    async fn reloads_the_owners_once_expired() {
+        let transport = Arc::new(MockTransport::new());
+        transport
+            .respond_json(StatusCode::OK, &page())
+            .respond_json(StatusCode::OK, &page());
+        let directory = owners(&transport).directory(Duration::ZERO);
+
+        directory.by_id("101").await.unwrap();
+        directory.by_id("101").await.unwrap();
+
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn reloads_the_owners_once_invalidated() {
+        let transport = Arc::new(MockTransport::new());
+        transport
+            .respond_json(StatusCode::OK, &page())
+            .respond_json(StatusCode::OK, &json!({ "results": [] }));
+        let directory = owners(&transport).directory(Duration::from_secs(60));
+
+        assert!(directory.clone().by_id("101").await.unwrap().is_some());
+        directory.invalidate().await;
+
+        assert!(directory.by_id("101").await.unwrap().is_none());
+        assert_eq!(transport.requests().len(), 2);
+    }
 }
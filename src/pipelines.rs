@@ -0,0 +1,608 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::{Arc, RwLock};
+
+use reqwest::Method;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_aux::field_attributes::{
+    deserialize_bool_from_anything, deserialize_option_number_from_string,
+};
+use time::OffsetDateTime;
+
+use crate::api_configs::query::Query;
+use crate::api_configs::types::{ListResult, ToPath};
+use crate::client::error::HubspotResult;
+use crate::client::HubspotClient;
+
+/// Whether tickets in a stage are open or closed.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TicketState {
+    Open,
+    Closed,
+}
+
+/// The metadata of a pipeline stage.
+///
+/// Hubspot sends and expects every value as a string, eg. `"0.2"` for a probability.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct StageMetadata {
+    /// The probability a deal in the stage closes, between 0 and 1. Required for deal stages.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_option_number_from_string",
+        serialize_with = "serialize_option_as_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub probability: Option<f64>,
+    /// Whether deals in the stage are closed.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_option_bool_from_anything",
+        serialize_with = "serialize_option_as_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub is_closed: Option<bool>,
+    /// Whether tickets in the stage are open or closed. Required for ticket stages.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ticket_state: Option<TicketState>,
+}
+
+impl StageMetadata {
+    /// The metadata of a deal stage.
+    pub fn deal(probability: f64, is_closed: bool) -> Self {
+        Self {
+            probability: Some(probability),
+            is_closed: Some(is_closed),
+            ticket_state: None,
+        }
+    }
+
+    /// The metadata of a ticket stage.
+    pub fn ticket(ticket_state: TicketState) -> Self {
+        Self {
+            ticket_state: Some(ticket_state),
+            ..Default::default()
+        }
+    }
+}
+
+fn serialize_option_as_string<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Display,
+    S: Serializer,
+{
+    match value {
+        Some(value) => serializer.collect_str(value),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn deserialize_option_bool_from_anything<'de, D>(deserializer: D) -> Result<Option<bool>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_bool_from_anything(deserializer).map(Some)
+}
+
+/// A stage of a pipeline, eg. "Closed Won".
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PipelineStage {
+    /// The ID of the stage, as stored in the `dealstage` or `hs_pipeline_stage` property.
+    pub id: String,
+    /// The label of the stage shown in Hubspot.
+    pub label: String,
+    /// The position of the stage within its pipeline.
+    pub display_order: i32,
+    /// The metadata of the stage.
+    #[serde(default)]
+    pub metadata: StageMetadata,
+    /// Whether the stage has been archived.
+    #[serde(default)]
+    pub archived: bool,
+    /// The date the stage was created.
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub created_at: Option<OffsetDateTime>,
+    /// The date the stage was last updated.
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub updated_at: Option<OffsetDateTime>,
+}
+
+/// A pipeline records are tracked through, eg. a sales process.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Pipeline {
+    /// The ID of the pipeline, as stored in the `pipeline` or `hs_pipeline` property.
+    pub id: String,
+    /// The label of the pipeline shown in Hubspot.
+    pub label: String,
+    /// The position of the pipeline.
+    pub display_order: i32,
+    /// The stages of the pipeline.
+    #[serde(default)]
+    pub stages: Vec<PipelineStage>,
+    /// Whether the pipeline has been archived.
+    #[serde(default)]
+    pub archived: bool,
+    /// The date the pipeline was created.
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub created_at: Option<OffsetDateTime>,
+    /// The date the pipeline was last updated.
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub updated_at: Option<OffsetDateTime>,
+}
+
+/// The input to create a pipeline stage.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PipelineStageInput {
+    /// The label of the stage shown in Hubspot.
+    pub label: String,
+    /// The position of the stage within its pipeline.
+    pub display_order: i32,
+    /// The metadata of the stage.
+    pub metadata: StageMetadata,
+}
+
+impl PipelineStageInput {
+    /// Create the input for a stage.
+    pub fn new(label: &str, display_order: i32, metadata: StageMetadata) -> Self {
+        Self {
+            label: label.to_owned(),
+            display_order,
+            metadata,
+        }
+    }
+}
+
+/// The input to create a pipeline.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PipelineInput {
+    /// The label of the pipeline shown in Hubspot.
+    pub label: String,
+    /// The position of the pipeline.
+    pub display_order: i32,
+    /// The stages of the pipeline.
+    pub stages: Vec<PipelineStageInput>,
+}
+
+impl PipelineInput {
+    /// Create the input for a pipeline without any stages.
+    pub fn new(label: &str, display_order: i32) -> Self {
+        Self {
+            label: label.to_owned(),
+            display_order,
+            stages: Vec::new(),
+        }
+    }
+
+    /// Add a stage to the pipeline.
+    pub fn stage(mut self, stage: PipelineStageInput) -> Self {
+        self.stages.push(stage);
+        self
+    }
+}
+
+/// The changes to make to a pipeline. Fields left as `None` are unchanged.
+#[derive(Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PipelineUpdate {
+    /// The label of the pipeline shown in Hubspot.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// The position of the pipeline.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_order: Option<i32>,
+    /// Set to `false` to restore an archived pipeline.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived: Option<bool>,
+}
+
+/// The changes to make to a pipeline stage. Fields left as `None` are unchanged.
+#[derive(Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PipelineStageUpdate {
+    /// The label of the stage shown in Hubspot.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// The position of the stage within its pipeline.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_order: Option<i32>,
+    /// The metadata of the stage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<StageMetadata>,
+    /// Set to `false` to restore an archived stage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived: Option<bool>,
+}
+
+/// Resolves the labels of pipelines and stages to their IDs and back.
+///
+/// Labels are matched ignoring ASCII case.
+#[derive(Debug, Clone)]
+pub struct StageLookup {
+    pipelines: Vec<Pipeline>,
+}
+
+impl StageLookup {
+    /// Build a lookup over the given pipelines.
+    pub fn new(pipelines: Vec<Pipeline>) -> Self {
+        Self { pipelines }
+    }
+
+    /// The pipelines of the lookup.
+    pub fn pipelines(&self) -> &[Pipeline] {
+        &self.pipelines
+    }
+
+    /// Returns the pipeline with the given ID or label.
+    pub fn pipeline(&self, pipeline: &str) -> Option<&Pipeline> {
+        self.pipelines
+            .iter()
+            .find(|p| p.id == pipeline)
+            .or_else(|| {
+                self.pipelines
+                    .iter()
+                    .find(|p| p.label.eq_ignore_ascii_case(pipeline))
+            })
+    }
+
+    /// Returns the ID of the pipeline with the given label.
+    pub fn pipeline_id(&self, label: &str) -> Option<&str> {
+        self.pipelines
+            .iter()
+            .find(|p| p.label.eq_ignore_ascii_case(label))
+            .map(|p| p.id.as_str())
+    }
+
+    /// Returns the ID of a stage by its label, within the pipeline with the given ID or label.
+    ///
+    /// Stage labels such as "Closed Won" are repeated across pipelines, so the pipeline is required.
+    pub fn stage_id(&self, pipeline: &str, label: &str) -> Option<&str> {
+        self.pipeline(pipeline)?
+            .stages
+            .iter()
+            .find(|s| s.label.eq_ignore_ascii_case(label))
+            .map(|s| s.id.as_str())
+    }
+
+    /// Returns the stage with the given ID and the pipeline it belongs to.
+    pub fn stage(&self, stage_id: &str) -> Option<(&Pipeline, &PipelineStage)> {
+        self.pipelines.iter().find_map(|p| {
+            p.stages
+                .iter()
+                .find(|s| s.id == stage_id)
+                .map(|stage| (p, stage))
+        })
+    }
+
+    /// Returns the label of the stage with the given ID.
+    pub fn stage_label(&self, stage_id: &str) -> Option<&str> {
+        self.stage(stage_id).map(|(_, stage)| stage.label.as_str())
+    }
+}
+
+/// Pipelines track records such as deals and tickets through stages,
+/// eg. a sales process from "Appointment Scheduled" to "Closed Won".
+#[derive(Clone, Debug)]
+pub struct PipelinesApi {
+    client: Arc<HubspotClient>,
+    /// Stage lookups cached by object type path.
+    lookups: Arc<RwLock<HashMap<String, Arc<StageLookup>>>>,
+}
+
+/// Implementation of Hubspot's Pipelines Api
+impl PipelinesApi {
+    /// Construct a new Pipelines API collection.
+    pub fn new(client: Arc<HubspotClient>) -> Self {
+        Self {
+            client: Arc::clone(&client),
+            lookups: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Returns a lookup of the pipelines and stages of an object type.
+    ///
+    /// The pipelines are fetched on first use and cached. Changes made through this api
+    /// clear the cache of the object type; use [PipelinesApi::invalidate] after changes made elsewhere.
+    pub async fn lookup<O>(&self, object_type: O) -> HubspotResult<Arc<StageLookup>>
+    where
+        O: ToPath,
+    {
+        let path = object_type.to_path();
+        if let Some(lookup) = self.lookups.read().unwrap().get(&path) {
+            return Ok(Arc::clone(lookup));
+        }
+
+        let lookup = Arc::new(StageLookup::new(self.list_path(&path, None).await?));
+        self.lookups
+            .write()
+            .unwrap()
+            .insert(path, Arc::clone(&lookup));
+        Ok(lookup)
+    }
+
+    /// Clear the cached lookup of an object type.
+    pub fn invalidate<O>(&self, object_type: O)
+    where
+        O: ToPath,
+    {
+        self.lookups.write().unwrap().remove(&object_type.to_path());
+    }
+
+    /// Returns every pipeline of an object type, including their stages.
+    pub async fn list<O>(
+        &self,
+        object_type: O,
+        archived: Option<bool>,
+    ) -> HubspotResult<Vec<Pipeline>>
+    where
+        O: ToPath,
+    {
+        self.list_path(&object_type.to_path(), archived).await
+    }
+
+    async fn list_path(&self, path: &str, archived: Option<bool>) -> HubspotResult<Vec<Pipeline>> {
+        self.client
+            .send::<ListResult<Pipeline>>(self.client.begin(
+                Method::GET,
+                &format!(
+                    "crm/v3/pipelines/{}{}",
+                    path,
+                    Query::new().push_opt("archived", archived)
+                ),
+            ))
+            .await
+            .map(|pipelines| pipelines.results)
+    }
+
+    /// Returns the pipeline of an object type by ID.
+    pub async fn read<O>(&self, object_type: O, pipeline_id: &str) -> HubspotResult<Pipeline>
+    where
+        O: ToPath,
+    {
+        self.client
+            .send::<Pipeline>(self.client.begin(
+                Method::GET,
+                &format!("crm/v3/pipelines/{}/{}", object_type.to_path(), pipeline_id),
+            ))
+            .await
+    }
+
+    /// Create a pipeline for an object type.
+    pub async fn create<O>(
+        &self,
+        object_type: O,
+        pipeline: PipelineInput,
+    ) -> HubspotResult<Pipeline>
+    where
+        O: ToPath,
+    {
+        let path = object_type.to_path();
+        let result = self
+            .client
+            .send::<Pipeline>(
+                self.client
+                    .begin(Method::POST, &format!("crm/v3/pipelines/{}", path))
+                    .json::<PipelineInput>(&pipeline),
+            )
+            .await;
+        self.lookups.write().unwrap().remove(&path);
+        result
+    }
+
+    /// Update the pipeline of an object type.
+    pub async fn update<O>(
+        &self,
+        object_type: O,
+        pipeline_id: &str,
+        update: PipelineUpdate,
+    ) -> HubspotResult<Pipeline>
+    where
+        O: ToPath,
+    {
+        let path = object_type.to_path();
+        let result = self
+            .client
+            .send::<Pipeline>(
+                self.client
+                    .begin(
+                        Method::PATCH,
+                        &format!("crm/v3/pipelines/{}/{}", path, pipeline_id),
+                    )
+                    .json::<PipelineUpdate>(&update),
+            )
+            .await;
+        self.lookups.write().unwrap().remove(&path);
+        result
+    }
+
+    /// Archive the pipeline of an object type.
+    pub async fn archive<O>(&self, object_type: O, pipeline_id: &str) -> HubspotResult<()>
+    where
+        O: ToPath,
+    {
+        let path = object_type.to_path();
+        let result = self
+            .client
+            .send(self.client.begin(
+                Method::DELETE,
+                &format!("crm/v3/pipelines/{}/{}", path, pipeline_id),
+            ))
+            .await;
+        self.lookups.write().unwrap().remove(&path);
+        result
+    }
+
+    /// Returns the stages of a pipeline.
+    pub async fn list_stages<O>(
+        &self,
+        object_type: O,
+        pipeline_id: &str,
+    ) -> HubspotResult<Vec<PipelineStage>>
+    where
+        O: ToPath,
+    {
+        self.client
+            .send::<ListResult<PipelineStage>>(self.client.begin(
+                Method::GET,
+                &format!(
+                    "crm/v3/pipelines/{}/{}/stages",
+                    object_type.to_path(),
+                    pipeline_id
+                ),
+            ))
+            .await
+            .map(|stages| stages.results)
+    }
+
+    /// Returns the stage of a pipeline by ID.
+    pub async fn read_stage<O>(
+        &self,
+        object_type: O,
+        pipeline_id: &str,
+        stage_id: &str,
+    ) -> HubspotResult<PipelineStage>
+    where
+        O: ToPath,
+    {
+        self.client
+            .send::<PipelineStage>(self.client.begin(
+                Method::GET,
+                &format!(
+                    "crm/v3/pipelines/{}/{}/stages/{}",
+                    object_type.to_path(),
+                    pipeline_id,
+                    stage_id
+                ),
+            ))
+            .await
+    }
+
+    /// Add a stage to a pipeline.
+    pub async fn create_stage<O>(
+        &self,
+        object_type: O,
+        pipeline_id: &str,
+        stage: PipelineStageInput,
+    ) -> HubspotResult<PipelineStage>
+    where
+        O: ToPath,
+    {
+        let path = object_type.to_path();
+        let result = self
+            .client
+            .send::<PipelineStage>(
+                self.client
+                    .begin(
+                        Method::POST,
+                        &format!("crm/v3/pipelines/{}/{}/stages", path, pipeline_id),
+                    )
+                    .json::<PipelineStageInput>(&stage),
+            )
+            .await;
+        self.lookups.write().unwrap().remove(&path);
+        result
+    }
+
+    /// Update the stage of a pipeline.
+    pub async fn update_stage<O>(
+        &self,
+        object_type: O,
+        pipeline_id: &str,
+        stage_id: &str,
+        update: PipelineStageUpdate,
+    ) -> HubspotResult<PipelineStage>
+    where
+        O: ToPath,
+    {
+        let path = object_type.to_path();
+        let result = self
+            .client
+            .send::<PipelineStage>(
+                self.client
+                    .begin(
+                        Method::PATCH,
+                        &format!(
+                            "crm/v3/pipelines/{}/{}/stages/{}",
+                            path, pipeline_id, stage_id
+                        ),
+                    )
+                    .json::<PipelineStageUpdate>(&update),
+            )
+            .await;
+        self.lookups.write().unwrap().remove(&path);
+        result
+    }
+
+    /// Archive the stage of a pipeline.
+    pub async fn archive_stage<O>(
+        &self,
+        object_type: O,
+        pipeline_id: &str,
+        stage_id: &str,
+    ) -> HubspotResult<()>
+    where
+        O: ToPath,
+    {
+        let path = object_type.to_path();
+        let result = self
+            .client
+            .send(self.client.begin(
+                Method::DELETE,
+                &format!(
+                    "crm/v3/pipelines/{}/{}/stages/{}",
+                    path, pipeline_id, stage_id
+                ),
+            ))
+            .await;
+        self.lookups.write().unwrap().remove(&path);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn serializes_stage_metadata_as_strings() {
+        assert_eq!(
+            serde_json::to_value(StageMetadata::deal(0.2, false)).unwrap(),
+            json!({ "probability": "0.2", "isClosed": "false" })
+        );
+        assert_eq!(
+            serde_json::to_value(StageMetadata::ticket(TicketState::Open)).unwrap(),
+            json!({ "ticketState": "OPEN" })
+        );
+    }
+
+    #[test]
+    fn deserializes_stage_metadata_from_strings() {
+        let deal: StageMetadata =
+            serde_json::from_value(json!({ "probability": "1.0", "isClosed": "true" })).unwrap();
+        assert_eq!(deal.probability, Some(1.0));
+        assert_eq!(deal.is_closed, Some(true));
+
+        let ticket: StageMetadata =
+            serde_json::from_value(json!({ "ticketState": "CLOSED", "isClosed": "false" }))
+                .unwrap();
+        assert_eq!(ticket.probability, None);
+        assert_eq!(ticket.is_closed, Some(false));
+        assert_eq!(ticket.ticket_state, Some(TicketState::Closed));
+    }
+
+    #[test]
+    fn deserializes_missing_stage_metadata() {
+        let metadata: StageMetadata = serde_json::from_value(json!({})).unwrap();
+
+        assert_eq!(metadata.probability, None);
+        assert_eq!(metadata.is_closed, None);
+        assert_eq!(metadata.ticket_state, None);
+    }
+}
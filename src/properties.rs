@@ -0,0 +1,576 @@
+use std::sync::Arc;
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::api_configs::query::Query;
+use crate::api_configs::types::{ListResult, ToPath};
+use crate::client::error::HubspotResult;
+use crate::client::HubspotClient;
+
+/// The data type of a property.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PropertyType {
+    String,
+    Number,
+    Date,
+    Datetime,
+    Enumeration,
+    Bool,
+    PhoneNumber,
+    ObjectCoordinates,
+    Json,
+    /// A type not yet known to this library.
+    #[serde(other)]
+    Other,
+}
+
+/// How a property is displayed and edited in Hubspot.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    Text,
+    Textarea,
+    Date,
+    File,
+    Number,
+    Select,
+    Radio,
+    Checkbox,
+    BooleanCheckbox,
+    #[serde(rename = "calculation_equation")]
+    CalculationEquation,
+    Html,
+    PhoneNumber,
+    /// A field type not yet known to this library.
+    #[serde(other)]
+    Other,
+}
+
+/// An option of an enumeration property.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PropertyOption {
+    /// The label of the option shown in Hubspot.
+    pub label: String,
+    /// The internal value of the option.
+    pub value: String,
+    /// A description of the option.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The position of the option. Options are sorted by ascending display order.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_order: Option<i32>,
+    /// Whether the option is hidden in Hubspot.
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+impl PropertyOption {
+    /// Create an option with the given label and value.
+    pub fn new(label: &str, value: &str) -> Self {
+        Self {
+            label: label.to_owned(),
+            value: value.to_owned(),
+            ..Default::default()
+        }
+    }
+}
+
+/// The definition of a property.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Property {
+    /// The internal name of the property.
+    pub name: String,
+    /// The label of the property shown in Hubspot.
+    pub label: String,
+    /// The data type of the property.
+    #[serde(rename = "type")]
+    pub property_type: PropertyType,
+    /// How the property is displayed and edited.
+    pub field_type: FieldType,
+    /// A description of the property.
+    #[serde(default)]
+    pub description: String,
+    /// The name of the property group the property belongs to.
+    #[serde(default)]
+    pub group_name: String,
+    /// The options of an enumeration property.
+    #[serde(default)]
+    pub options: Vec<PropertyOption>,
+    /// The position of the property within its group.
+    pub display_order: Option<i32>,
+    /// Whether the property's value is calculated by Hubspot.
+    #[serde(default)]
+    pub calculated: bool,
+    /// Whether the property's value must be unique across records.
+    #[serde(default)]
+    pub has_unique_value: bool,
+    /// Whether the property is hidden in Hubspot.
+    #[serde(default)]
+    pub hidden: bool,
+    /// Whether the property is a default property defined by Hubspot.
+    #[serde(default)]
+    pub hubspot_defined: bool,
+    /// Whether the property can be used in forms.
+    #[serde(default)]
+    pub form_field: bool,
+    /// Whether the property has been archived.
+    #[serde(default)]
+    pub archived: bool,
+    /// The date the property was created.
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub created_at: Option<OffsetDateTime>,
+    /// The date the property was last updated.
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub updated_at: Option<OffsetDateTime>,
+}
+
+/// The input to create a new property.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PropertyInput {
+    /// The internal name of the property.
+    pub name: String,
+    /// The label of the property shown in Hubspot.
+    pub label: String,
+    /// The data type of the property.
+    #[serde(rename = "type")]
+    pub property_type: PropertyType,
+    /// How the property is displayed and edited.
+    pub field_type: FieldType,
+    /// The name of the property group the property belongs to.
+    pub group_name: String,
+    /// A description of the property.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The options of an enumeration property.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<PropertyOption>,
+    /// The position of the property within its group.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_order: Option<i32>,
+    /// Whether the property's value must be unique across records, eg. an external ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_unique_value: Option<bool>,
+    /// Whether the property is hidden in Hubspot.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hidden: Option<bool>,
+    /// Whether the property can be used in forms.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub form_field: Option<bool>,
+}
+
+impl PropertyInput {
+    /// Create the input for a property in the given group.
+    pub fn new(
+        name: &str,
+        label: &str,
+        property_type: PropertyType,
+        field_type: FieldType,
+        group_name: &str,
+    ) -> Self {
+        Self {
+            name: name.to_owned(),
+            label: label.to_owned(),
+            property_type,
+            field_type,
+            group_name: group_name.to_owned(),
+            description: None,
+            options: Vec::new(),
+            display_order: None,
+            has_unique_value: None,
+            hidden: None,
+            form_field: None,
+        }
+    }
+
+    /// Add an option to an enumeration property.
+    pub fn option(mut self, option: PropertyOption) -> Self {
+        self.options.push(option);
+        self
+    }
+}
+
+/// The changes to make to a property. Fields left as `None` are unchanged.
+#[derive(Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PropertyUpdate {
+    /// The label of the property shown in Hubspot.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// The data type of the property.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub property_type: Option<PropertyType>,
+    /// How the property is displayed and edited.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field_type: Option<FieldType>,
+    /// The name of the property group the property belongs to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_name: Option<String>,
+    /// A description of the property.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The options of an enumeration property. Replaces the existing options.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<Vec<PropertyOption>>,
+    /// The position of the property within its group.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_order: Option<i32>,
+    /// Whether the property is hidden in Hubspot.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hidden: Option<bool>,
+    /// Whether the property can be used in forms.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub form_field: Option<bool>,
+}
+
+/// A group of related properties.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PropertyGroup {
+    /// The internal name of the group.
+    pub name: String,
+    /// The label of the group shown in Hubspot.
+    pub label: String,
+    /// The position of the group. Groups are sorted by ascending display order.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_order: Option<i32>,
+    /// Whether the group has been archived.
+    #[serde(default, skip_serializing)]
+    pub archived: bool,
+}
+
+impl PropertyGroup {
+    /// Create a property group with the given name and label.
+    pub fn new(name: &str, label: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            label: label.to_owned(),
+            ..Default::default()
+        }
+    }
+}
+
+/// The changes to make to a property group. Fields left as `None` are unchanged.
+#[derive(Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PropertyGroupUpdate {
+    /// The label of the group shown in Hubspot.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// The position of the group.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_order: Option<i32>,
+}
+
+/// A wrapper type for property batch inputs.
+#[derive(Serialize, Debug)]
+struct PropertyBatchInputs<I> {
+    inputs: Vec<I>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    archived: Option<bool>,
+}
+
+/// A property name for the batch read and archive apis.
+#[derive(Serialize, Debug)]
+struct PropertyName {
+    name: String,
+}
+
+/// Properties store information about records, eg. a contact's email or a deal's amount.
+/// Each object type has its own properties, organised into property groups.
+#[derive(Clone, Debug)]
+pub struct PropertiesApi {
+    client: Arc<HubspotClient>,
+}
+
+/// Implementation of Hubspot's Properties Api
+impl PropertiesApi {
+    /// Construct a new Properties API collection.
+    pub fn new(client: Arc<HubspotClient>) -> Self {
+        Self {
+            client: Arc::clone(&client),
+        }
+    }
+
+    /// Returns every property of an object type.
+    pub async fn list<O>(
+        &self,
+        object_type: O,
+        archived: Option<bool>,
+    ) -> HubspotResult<Vec<Property>>
+    where
+        O: ToPath,
+    {
+        self.client
+            .send::<ListResult<Property>>(self.client.begin(
+                Method::GET,
+                &format!(
+                    "crm/v3/properties/{}{}",
+                    object_type.to_path(),
+                    Query::new().push("archived", archived.unwrap_or(false))
+                ),
+            ))
+            .await
+            .map(|properties| properties.results)
+    }
+
+    /// Returns the property of an object type by name.
+    pub async fn read<O>(
+        &self,
+        object_type: O,
+        name: &str,
+        archived: Option<bool>,
+    ) -> HubspotResult<Property>
+    where
+        O: ToPath,
+    {
+        self.client
+            .send::<Property>(self.client.begin(
+                Method::GET,
+                &format!(
+                    "crm/v3/properties/{}/{}{}",
+                    object_type.to_path(),
+                    name,
+                    Query::new().push("archived", archived.unwrap_or(false))
+                ),
+            ))
+            .await
+    }
+
+    /// Create a property for an object type.
+    pub async fn create<O>(
+        &self,
+        object_type: O,
+        property: PropertyInput,
+    ) -> HubspotResult<Property>
+    where
+        O: ToPath,
+    {
+        self.client
+            .send::<Property>(
+                self.client
+                    .begin(
+                        Method::POST,
+                        &format!("crm/v3/properties/{}", object_type.to_path()),
+                    )
+                    .json::<PropertyInput>(&property),
+            )
+            .await
+    }
+
+    /// Update the property of an object type.
+    pub async fn update<O>(
+        &self,
+        object_type: O,
+        name: &str,
+        update: PropertyUpdate,
+    ) -> HubspotResult<Property>
+    where
+        O: ToPath,
+    {
+        self.client
+            .send::<Property>(
+                self.client
+                    .begin(
+                        Method::PATCH,
+                        &format!("crm/v3/properties/{}/{}", object_type.to_path(), name),
+                    )
+                    .json::<PropertyUpdate>(&update),
+            )
+            .await
+    }
+
+    /// Move the property of an object type to the recycling bin.
+    pub async fn archive<O>(&self, object_type: O, name: &str) -> HubspotResult<()>
+    where
+        O: ToPath,
+    {
+        self.client
+            .send(self.client.begin(
+                Method::DELETE,
+                &format!("crm/v3/properties/{}/{}", object_type.to_path(), name),
+            ))
+            .await
+    }
+
+    /// Create a batch of properties for an object type.
+    pub async fn batch_create<O>(
+        &self,
+        object_type: O,
+        properties: Vec<PropertyInput>,
+    ) -> HubspotResult<Vec<Property>>
+    where
+        O: ToPath,
+    {
+        self.client
+            .send::<ListResult<Property>>(
+                self.client
+                    .begin(
+                        Method::POST,
+                        &format!("crm/v3/properties/{}/batch/create", object_type.to_path()),
+                    )
+                    .json::<PropertyBatchInputs<PropertyInput>>(&PropertyBatchInputs {
+                        inputs: properties,
+                        archived: None,
+                    }),
+            )
+            .await
+            .map(|properties| properties.results)
+    }
+
+    /// Read a batch of the properties of an object type by name.
+    pub async fn batch_read<O>(
+        &self,
+        object_type: O,
+        names: Vec<&str>,
+        archived: Option<bool>,
+    ) -> HubspotResult<Vec<Property>>
+    where
+        O: ToPath,
+    {
+        self.client
+            .send::<ListResult<Property>>(
+                self.client
+                    .begin(
+                        Method::POST,
+                        &format!("crm/v3/properties/{}/batch/read", object_type.to_path()),
+                    )
+                    .json::<PropertyBatchInputs<PropertyName>>(&PropertyBatchInputs {
+                        inputs: names
+                            .into_iter()
+                            .map(|name| PropertyName {
+                                name: name.to_owned(),
+                            })
+                            .collect(),
+                        archived: Some(archived.unwrap_or(false)),
+                    }),
+            )
+            .await
+            .map(|properties| properties.results)
+    }
+
+    /// Archive a batch of the properties of an object type by name.
+    pub async fn batch_archive<O>(&self, object_type: O, names: Vec<&str>) -> HubspotResult<()>
+    where
+        O: ToPath,
+    {
+        self.client
+            .send(
+                self.client
+                    .begin(
+                        Method::POST,
+                        &format!("crm/v3/properties/{}/batch/archive", object_type.to_path()),
+                    )
+                    .json::<PropertyBatchInputs<PropertyName>>(&PropertyBatchInputs {
+                        inputs: names
+                            .into_iter()
+                            .map(|name| PropertyName {
+                                name: name.to_owned(),
+                            })
+                            .collect(),
+                        archived: None,
+                    }),
+            )
+            .await
+    }
+
+    /// Returns every property group of an object type.
+    pub async fn list_groups<O>(&self, object_type: O) -> HubspotResult<Vec<PropertyGroup>>
+    where
+        O: ToPath,
+    {
+        self.client
+            .send::<ListResult<PropertyGroup>>(self.client.begin(
+                Method::GET,
+                &format!("crm/v3/properties/{}/groups", object_type.to_path()),
+            ))
+            .await
+            .map(|groups| groups.results)
+    }
+
+    /// Returns the property group of an object type by name.
+    pub async fn read_group<O>(&self, object_type: O, name: &str) -> HubspotResult<PropertyGroup>
+    where
+        O: ToPath,
+    {
+        self.client
+            .send::<PropertyGroup>(self.client.begin(
+                Method::GET,
+                &format!(
+                    "crm/v3/properties/{}/groups/{}",
+                    object_type.to_path(),
+                    name
+                ),
+            ))
+            .await
+    }
+
+    /// Create a property group for an object type.
+    pub async fn create_group<O>(
+        &self,
+        object_type: O,
+        group: PropertyGroup,
+    ) -> HubspotResult<PropertyGroup>
+    where
+        O: ToPath,
+    {
+        self.client
+            .send::<PropertyGroup>(
+                self.client
+                    .begin(
+                        Method::POST,
+                        &format!("crm/v3/properties/{}/groups", object_type.to_path()),
+                    )
+                    .json::<PropertyGroup>(&group),
+            )
+            .await
+    }
+
+    /// Update the property group of an object type.
+    pub async fn update_group<O>(
+        &self,
+        object_type: O,
+        name: &str,
+        update: PropertyGroupUpdate,
+    ) -> HubspotResult<PropertyGroup>
+    where
+        O: ToPath,
+    {
+        self.client
+            .send::<PropertyGroup>(
+                self.client
+                    .begin(
+                        Method::PATCH,
+                        &format!(
+                            "crm/v3/properties/{}/groups/{}",
+                            object_type.to_path(),
+                            name
+                        ),
+                    )
+                    .json::<PropertyGroupUpdate>(&update),
+            )
+            .await
+    }
+
+    /// Move the property group of an object type to the recycling bin.
+    pub async fn archive_group<O>(&self, object_type: O, name: &str) -> HubspotResult<()>
+    where
+        O: ToPath,
+    {
+        self.client
+            .send(self.client.begin(
+                Method::DELETE,
+                &format!(
+                    "crm/v3/properties/{}/groups/{}",
+                    object_type.to_path(),
+                    name
+                ),
+            ))
+            .await
+    }
+}
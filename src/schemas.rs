@@ -0,0 +1,285 @@
+use std::sync::Arc;
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::api_configs::query::Query;
+use crate::api_configs::types::ListResult;
+use crate::client::error::HubspotResult;
+use crate::client::HubspotClient;
+use crate::ObjectType;
+
+/// The singular and plural labels of an object type.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ObjectTypeLabels {
+    /// The word for one object, eg. "Project".
+    pub singular: Option<String>,
+    /// The word for multiple objects, eg. "Projects".
+    pub plural: Option<String>,
+}
+
+/// A property of a custom object schema.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaProperty {
+    /// The internal name of the property.
+    pub name: String,
+    /// The label of the property shown in Hubspot.
+    pub label: String,
+    /// The data type of the property, eg. `string`, `number` or `enumeration`.
+    #[serde(rename = "type")]
+    pub property_type: String,
+    /// How the property is displayed and edited, eg. `text`, `select` or `date`.
+    pub field_type: String,
+    /// The name of the property group the property belongs to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_name: Option<String>,
+    /// A description of the property.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether the property's value must be unique across records, eg. an external ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_unique_value: Option<bool>,
+}
+
+/// An association definition between a custom object and another object type.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AssociationDefinition {
+    /// The ID of the association definition.
+    pub id: String,
+    /// The object type ID of the source object.
+    pub from_object_type_id: String,
+    /// The object type ID of the associated object.
+    pub to_object_type_id: String,
+    /// The name of the association definition.
+    pub name: Option<String>,
+}
+
+/// The input to define an association between a custom object and another object type.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AssociationDefinitionInput {
+    /// The object type ID of the source object, eg. `2-123456`.
+    pub from_object_type_id: String,
+    /// The object type ID of the associated object, eg. `0-1` for contacts.
+    pub to_object_type_id: String,
+    /// A unique name for the association definition.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// The definition of a custom object type.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectSchema {
+    /// The ID of the schema.
+    pub id: String,
+    /// The name of the object type.
+    pub name: String,
+    /// The object type ID, eg. `2-123456`.
+    pub object_type_id: String,
+    /// The fully qualified name of the object type, eg. `p123456_projects`.
+    pub fully_qualified_name: String,
+    /// The labels of the object type.
+    #[serde(default)]
+    pub labels: ObjectTypeLabels,
+    /// A description of the object type.
+    pub description: Option<String>,
+    /// The property displayed as the name of a record.
+    pub primary_display_property: Option<String>,
+    /// Properties displayed below the name of a record.
+    #[serde(default)]
+    pub secondary_display_properties: Vec<String>,
+    /// Properties indexed for searching.
+    #[serde(default)]
+    pub searchable_properties: Vec<String>,
+    /// Properties required to create a record.
+    #[serde(default)]
+    pub required_properties: Vec<String>,
+    /// The properties of the object type.
+    #[serde(default)]
+    pub properties: Vec<SchemaProperty>,
+    /// The object types the object can be associated with.
+    #[serde(default)]
+    pub associations: Vec<AssociationDefinition>,
+    /// Whether the schema has been archived.
+    #[serde(default)]
+    pub archived: bool,
+    /// The date the schema was created.
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub created_at: Option<OffsetDateTime>,
+    /// The date the schema was last updated.
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub updated_at: Option<OffsetDateTime>,
+}
+
+impl ObjectSchema {
+    /// The object type of the schema's records, to build its collection.
+    pub fn object_type(&self) -> ObjectType {
+        ObjectType::Custom(self.object_type_id.clone())
+    }
+}
+
+/// The input to define a new custom object type.
+#[derive(Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectSchemaInput {
+    /// The name of the object type.
+    pub name: String,
+    /// The labels of the object type.
+    pub labels: ObjectTypeLabels,
+    /// A description of the object type.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The property displayed as the name of a record.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary_display_property: Option<String>,
+    /// Properties displayed below the name of a record.
+    pub secondary_display_properties: Vec<String>,
+    /// Properties indexed for searching.
+    pub searchable_properties: Vec<String>,
+    /// Properties required to create a record.
+    pub required_properties: Vec<String>,
+    /// The properties of the object type.
+    pub properties: Vec<SchemaProperty>,
+    /// The object types the object can be associated with, eg. `CONTACT` or an object type ID.
+    pub associated_objects: Vec<String>,
+}
+
+/// The changes to make to a custom object type. Fields left as `None` are unchanged.
+#[derive(Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectSchemaUpdate {
+    /// The labels of the object type.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<ObjectTypeLabels>,
+    /// A description of the object type.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The property displayed as the name of a record.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary_display_property: Option<String>,
+    /// Properties displayed below the name of a record.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secondary_display_properties: Option<Vec<String>>,
+    /// Properties indexed for searching.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub searchable_properties: Option<Vec<String>>,
+    /// Properties required to create a record.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required_properties: Option<Vec<String>>,
+}
+
+/// Custom object schemas define the object types of a portal beyond the standard objects,
+/// including their properties, display properties and allowed associations.
+#[derive(Clone, Debug)]
+pub struct SchemasApi {
+    client: Arc<HubspotClient>,
+}
+
+/// Implementation of Hubspot's Schemas Api
+impl SchemasApi {
+    /// Construct a new Schemas API collection.
+    pub fn new(client: Arc<HubspotClient>) -> Self {
+        Self {
+            client: Arc::clone(&client),
+        }
+    }
+
+    /// Returns every custom object schema of the account.
+    pub async fn list(&self, archived: Option<bool>) -> HubspotResult<Vec<ObjectSchema>> {
+        self.client
+            .send::<ListResult<ObjectSchema>>(self.client.begin(
+                Method::GET,
+                &format!(
+                    "crm/v3/schemas{}",
+                    Query::new().push("archived", archived.unwrap_or(false))
+                ),
+            ))
+            .await
+            .map(|schemas| schemas.results)
+    }
+
+    /// Returns the schema for the object type ID or fully qualified name.
+    pub async fn read(&self, object_type: &str) -> HubspotResult<ObjectSchema> {
+        self.client
+            .send::<ObjectSchema>(
+                self.client
+                    .begin(Method::GET, &format!("crm/v3/schemas/{}", object_type)),
+            )
+            .await
+    }
+
+    /// Define a new custom object type.
+    pub async fn create(&self, schema: ObjectSchemaInput) -> HubspotResult<ObjectSchema> {
+        self.client
+            .send::<ObjectSchema>(
+                self.client
+                    .begin(Method::POST, "crm/v3/schemas")
+                    .json::<ObjectSchemaInput>(&schema),
+            )
+            .await
+    }
+
+    /// Update the definition of a custom object type.
+    pub async fn update(
+        &self,
+        object_type: &str,
+        update: ObjectSchemaUpdate,
+    ) -> HubspotResult<ObjectSchema> {
+        self.client
+            .send::<ObjectSchema>(
+                self.client
+                    .begin(Method::PATCH, &format!("crm/v3/schemas/{}", object_type))
+                    .json::<ObjectSchemaUpdate>(&update),
+            )
+            .await
+    }
+
+    /// Archive a custom object type. Its records must be archived first.
+    pub async fn archive(&self, object_type: &str) -> HubspotResult<()> {
+        self.client
+            .send(
+                self.client
+                    .begin(Method::DELETE, &format!("crm/v3/schemas/{}", object_type)),
+            )
+            .await
+    }
+
+    /// Define an association between a custom object and another object type.
+    pub async fn create_association(
+        &self,
+        object_type: &str,
+        association: AssociationDefinitionInput,
+    ) -> HubspotResult<AssociationDefinition> {
+        self.client
+            .send::<AssociationDefinition>(
+                self.client
+                    .begin(
+                        Method::POST,
+                        &format!("crm/v3/schemas/{}/associations", object_type),
+                    )
+                    .json::<AssociationDefinitionInput>(&association),
+            )
+            .await
+    }
+
+    /// Remove an association definition from a custom object type.
+    pub async fn archive_association(
+        &self,
+        object_type: &str,
+        association_id: &str,
+    ) -> HubspotResult<()> {
+        self.client
+            .send(self.client.begin(
+                Method::DELETE,
+                &format!(
+                    "crm/v3/schemas/{}/associations/{}",
+                    object_type, association_id
+                ),
+            ))
+            .await
+    }
+}